@@ -0,0 +1,226 @@
+use crate::errors::{Error, ExtractResult};
+use std::collections::{HashMap, HashSet};
+
+/// Magic bytes at the start of every OLE2 / Compound File Binary (CFB) container.
+/// Legacy Office embeddings (and OLE object wrappers around modern OOXML files) are
+/// framed this way.
+pub(crate) const CFB_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+const FREESECT: u32 = 0xFFFFFFFF;
+const ENDOFCHAIN: u32 = 0xFFFFFFFE;
+
+const HEADER_SIZE: usize = 512;
+const DIR_ENTRY_SIZE: usize = 128;
+
+/// A directory entry inside a CFB container (a stream, storage, or the root entry).
+struct DirEntry {
+    name: String,
+    object_type: u8,
+    start_sector: u32,
+    stream_size: u64,
+}
+
+/// A parsed CFB container, giving name-based access to its streams.
+///
+/// This only implements the subset of the spec needed to pull a handful of named
+/// streams (`Package`, `\x01Ole`, `\x01CompObj`) back out of an OLE2 wrapper - it is
+/// not a general-purpose CFB reader/writer.
+pub(crate) struct CompoundFile {
+    streams: HashMap<String, Vec<u8>>,
+}
+
+impl CompoundFile {
+    /// Parses `data` as a CFB container. Returns `Ok(None)` (rather than an error)
+    /// when `data` doesn't start with the CFB magic, so callers can cheaply treat
+    /// that as "not an OLE wrapper".
+    pub(crate) fn parse(data: &[u8]) -> ExtractResult<Option<Self>> {
+        if data.len() < HEADER_SIZE || data[0..8] != CFB_MAGIC {
+            return Ok(None);
+        }
+
+        let sector_shift = u16::from_le_bytes([data[30], data[31]]);
+        let sector_size = 1usize << sector_shift;
+        let mini_sector_shift = u16::from_le_bytes([data[32], data[33]]);
+        let mini_sector_size = 1usize << mini_sector_shift;
+        // Both counts come straight off the untrusted header and are used below
+        // to size allocations (`fat_sector_locations`'s capacity, and the DIFAT
+        // walk's iteration count) - a crafted container can claim far more
+        // sectors than `data` could possibly hold, so clamp each against the
+        // most sectors that could conceivably fit in the buffer before trusting
+        // it, the same way `name_len` is clamped in `parse_dir_entries`.
+        let max_sectors = data.len() / sector_size;
+        let num_fat_sectors = (read_u32(data, 44) as usize).min(max_sectors);
+        let first_dir_sector = read_u32(data, 48);
+        let mini_stream_cutoff = read_u32(data, 56) as u64;
+        let first_minifat_sector = read_u32(data, 60);
+        let num_minifat_sectors = read_u32(data, 64);
+        let first_difat_sector = read_u32(data, 68);
+        let num_difat_sectors = (read_u32(data, 72) as usize).min(max_sectors);
+
+        // The first 109 FAT sector locations live in the header; any more chain
+        // through dedicated DIFAT sectors.
+        let mut fat_sector_locations = Vec::with_capacity(num_fat_sectors);
+        for i in 0..109.min(num_fat_sectors) {
+            fat_sector_locations.push(read_u32(data, 76 + i * 4));
+        }
+        if num_difat_sectors > 0 {
+            let mut sector = first_difat_sector;
+            let entries_per_sector = sector_size / 4 - 1;
+            for _ in 0..num_difat_sectors {
+                let bytes = read_sector(data, sector, sector_size)?;
+                for i in 0..entries_per_sector {
+                    let entry = read_u32(bytes, i * 4);
+                    if entry != FREESECT {
+                        fat_sector_locations.push(entry);
+                    }
+                }
+                sector = read_u32(bytes, entries_per_sector * 4);
+                if sector == ENDOFCHAIN || sector == FREESECT {
+                    break;
+                }
+            }
+        }
+
+        let mut fat = Vec::new();
+        for &sector in &fat_sector_locations {
+            let bytes = read_sector(data, sector, sector_size)?;
+            for chunk in bytes.chunks_exact(4) {
+                fat.push(u32::from_le_bytes(chunk.try_into().unwrap()));
+            }
+        }
+
+        let dir_bytes = read_chain(data, first_dir_sector, sector_size, &fat)?;
+        let entries = parse_dir_entries(&dir_bytes);
+
+        // The mini stream (backing small streams) lives in the root entry's own
+        // regular sector chain.
+        let root = entries
+            .iter()
+            .find(|e| e.object_type == 5)
+            .ok_or_else(|| Error::ParseError("OLE2 file has no root directory entry".to_string()))?;
+        let mini_stream = read_chain(data, root.start_sector, sector_size, &fat)?;
+
+        let minifat = if num_minifat_sectors > 0 {
+            read_chain(data, first_minifat_sector, sector_size, &fat)?
+                .chunks_exact(4)
+                .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        let mut streams = HashMap::new();
+        for entry in &entries {
+            if entry.object_type != 2 || entry.name.is_empty() {
+                continue; // only streams carry content; skip storages and the root
+            }
+            let content = if entry.stream_size < mini_stream_cutoff {
+                read_mini_chain(
+                    &mini_stream,
+                    entry.start_sector,
+                    mini_sector_size,
+                    &minifat,
+                    entry.stream_size,
+                )
+            } else {
+                let mut bytes = read_chain(data, entry.start_sector, sector_size, &fat)?;
+                bytes.truncate(entry.stream_size as usize);
+                bytes
+            };
+            streams.insert(entry.name.clone(), content);
+        }
+
+        Ok(Some(Self { streams }))
+    }
+
+    /// Looks up a stream by its CFB entry name (e.g. `"Package"`, `"\u{1}CompObj"`).
+    pub(crate) fn stream(&self, name: &str) -> Option<&[u8]> {
+        self.streams.get(name).map(|v| v.as_slice())
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_sector(data: &[u8], sector: u32, sector_size: usize) -> ExtractResult<&[u8]> {
+    let start = HEADER_SIZE + sector as usize * sector_size;
+    data.get(start..start + sector_size)
+        .ok_or_else(|| Error::ParseError("OLE2 sector out of bounds".to_string()))
+}
+
+fn read_chain(data: &[u8], start: u32, sector_size: usize, fat: &[u32]) -> ExtractResult<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut sector = start;
+    let mut visited = HashSet::new();
+    while sector != ENDOFCHAIN && sector != FREESECT {
+        if !visited.insert(sector) {
+            return Err(Error::ParseError("OLE2 sector chain cycle detected".to_string()));
+        }
+        out.extend_from_slice(read_sector(data, sector, sector_size)?);
+        sector = *fat
+            .get(sector as usize)
+            .ok_or_else(|| Error::ParseError("OLE2 FAT chain out of bounds".to_string()))?;
+    }
+    Ok(out)
+}
+
+fn read_mini_chain(
+    mini_stream: &[u8],
+    start: u32,
+    mini_sector_size: usize,
+    minifat: &[u32],
+    size: u64,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut sector = start;
+    let mut visited = HashSet::new();
+    while sector != ENDOFCHAIN && sector != FREESECT {
+        if !visited.insert(sector) {
+            break;
+        }
+        let start_off = sector as usize * mini_sector_size;
+        let end_off = start_off + mini_sector_size;
+        if end_off > mini_stream.len() {
+            break;
+        }
+        out.extend_from_slice(&mini_stream[start_off..end_off]);
+        sector = match minifat.get(sector as usize) {
+            Some(&s) => s,
+            None => break,
+        };
+    }
+    out.truncate(size as usize);
+    out
+}
+
+fn parse_dir_entries(dir_bytes: &[u8]) -> Vec<DirEntry> {
+    dir_bytes
+        .chunks_exact(DIR_ENTRY_SIZE)
+        .filter_map(|entry| {
+            let object_type = entry[66];
+            if object_type == 0 {
+                return None; // unused entry
+            }
+            // The directory entry name field is fixed at 64 bytes; a CFB-spec-compliant
+            // name_len never exceeds that, but a malformed/adversarial container can claim
+            // otherwise, so clamp before slicing rather than trusting it.
+            let name_len = (u16::from_le_bytes([entry[64], entry[65]]) as usize).min(64);
+            let name = if name_len < 2 {
+                String::new()
+            } else {
+                let utf16: Vec<u16> = entry[0..name_len - 2]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16_lossy(&utf16)
+            };
+            Some(DirEntry {
+                name,
+                object_type,
+                start_sector: read_u32(entry, 116),
+                stream_size: u64::from_le_bytes(entry[120..128].try_into().unwrap()),
+            })
+        })
+        .collect()
+}
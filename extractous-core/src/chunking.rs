@@ -0,0 +1,195 @@
+use crate::errors::{Error, ExtractResult};
+use crate::Metadata;
+
+/// Configuration for splitting extracted text into overlapping [`Chunk`]s suitable
+/// for feeding to an embedding/search pipeline.
+#[derive(Debug, Clone)]
+pub struct ChunkConfig {
+    /// Maximum number of characters per chunk.
+    pub max_chars: usize,
+    /// Number of characters from the end of one chunk that are repeated at the
+    /// start of the next, so context survives across a chunk boundary.
+    pub overlap_chars: usize,
+    /// When set, prefer breaking at a paragraph/sentence (or, for XML input,
+    /// structural element) boundary near `max_chars` rather than mid-word.
+    pub respect_boundaries: bool,
+}
+
+impl ChunkConfig {
+    pub fn new(max_chars: usize, overlap_chars: usize) -> Self {
+        Self {
+            max_chars,
+            overlap_chars,
+            respect_boundaries: true,
+        }
+    }
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self::new(2000, 200)
+    }
+}
+
+/// A single slice of extracted content, with its position in the original text
+/// and a copy of the document metadata so it can be indexed independently.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    /// The chunk's text content.
+    pub text: String,
+    /// Start character offset (inclusive) into the original extracted text.
+    pub start_char: usize,
+    /// End character offset (exclusive) into the original extracted text.
+    pub end_char: usize,
+    /// A clone of the document's metadata, attached to every chunk so callers
+    /// can index chunks independently without keeping the original document around.
+    pub metadata: Metadata,
+}
+
+/// XML structural element names that [`chunk_xml`] prefers to break on when
+/// `respect_boundaries` is set; [`chunk_text`] does not use them.
+const XML_BOUNDARY_TAGS: &[&str] = &["</p>", "</div>", "</h1>", "</h2>", "</h3>", "</h4>", "</h5>", "</h6>"];
+
+/// Splits `text` into overlapping chunks according to `config`, cloning `metadata`
+/// onto every chunk produced.
+///
+/// This is the plain-text splitter: boundaries are paragraph breaks (`\n\n`) and
+/// sentence ends (`. `, `? `, `! `). Use [`chunk_xml`] instead when `text` is the
+/// XML output of an extraction (i.e. `Extractor::set_xml_output(true)` was used),
+/// so structural tags are preferred over raw character counts.
+pub fn chunk_text(text: &str, metadata: &Metadata, config: &ChunkConfig) -> ExtractResult<Vec<Chunk>> {
+    validate_config(config)?;
+
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+
+    while start < len {
+        let ideal_end = (start + config.max_chars).min(len);
+        let end = if ideal_end >= len {
+            len
+        } else if config.respect_boundaries {
+            find_text_boundary(&chars, start, ideal_end).unwrap_or(ideal_end)
+        } else {
+            ideal_end
+        };
+
+        let chunk_text: String = chars[start..end].iter().collect();
+        chunks.push(Chunk {
+            text: chunk_text,
+            start_char: start,
+            end_char: end,
+            metadata: metadata.clone(),
+        });
+
+        if end >= len {
+            break;
+        }
+
+        // Start the next chunk `overlap_chars` before this break so context
+        // carries over, but always make forward progress.
+        start = end.saturating_sub(config.overlap_chars).max(start + 1);
+    }
+
+    Ok(chunks)
+}
+
+/// Like [`chunk_text`], but prefers breaking on XML structural element
+/// boundaries (`</p>`, `</div>`, `</h1>`..`</h6>`) instead of raw sentence
+/// punctuation, for use with XML-mode extraction output.
+pub fn chunk_xml(text: &str, metadata: &Metadata, config: &ChunkConfig) -> ExtractResult<Vec<Chunk>> {
+    validate_config(config)?;
+
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+
+    while start < len {
+        let ideal_end = (start + config.max_chars).min(len);
+        let end = if ideal_end >= len {
+            len
+        } else if config.respect_boundaries {
+            find_xml_boundary(&chars, start, ideal_end).unwrap_or(ideal_end)
+        } else {
+            ideal_end
+        };
+
+        let chunk_text: String = chars[start..end].iter().collect();
+        chunks.push(Chunk {
+            text: chunk_text,
+            start_char: start,
+            end_char: end,
+            metadata: metadata.clone(),
+        });
+
+        if end >= len {
+            break;
+        }
+
+        start = end.saturating_sub(config.overlap_chars).max(start + 1);
+    }
+
+    Ok(chunks)
+}
+
+fn validate_config(config: &ChunkConfig) -> ExtractResult<()> {
+    if config.max_chars == 0 {
+        return Err(Error::ParseError(
+            "ChunkConfig::max_chars must be greater than zero".to_string(),
+        ));
+    }
+    if config.overlap_chars >= config.max_chars {
+        return Err(Error::ParseError(
+            "ChunkConfig::overlap_chars must be smaller than max_chars".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Searches backwards from `ideal_end` (bounded by `start`) for the nearest
+/// paragraph break, falling back to a sentence end, so a chunk doesn't split
+/// mid-word.
+fn find_text_boundary(chars: &[char], start: usize, ideal_end: usize) -> Option<usize> {
+    // Prefer a double-newline (paragraph break).
+    let mut i = ideal_end;
+    while i > start + 1 {
+        if chars[i - 1] == '\n' && chars[i - 2] == '\n' {
+            return Some(i);
+        }
+        i -= 1;
+    }
+
+    // Fall back to a sentence end: `.`, `?`, or `!` followed by whitespace.
+    let mut i = ideal_end;
+    while i > start + 1 {
+        let c = chars[i - 2];
+        if (c == '.' || c == '?' || c == '!') && chars[i - 1].is_whitespace() {
+            return Some(i);
+        }
+        i -= 1;
+    }
+
+    None
+}
+
+/// Searches backwards from `ideal_end` (bounded by `start`) for the end of the
+/// nearest XML structural element in [`XML_BOUNDARY_TAGS`].
+fn find_xml_boundary(chars: &[char], start: usize, ideal_end: usize) -> Option<usize> {
+    let window: String = chars[start..ideal_end].iter().collect();
+
+    XML_BOUNDARY_TAGS
+        .iter()
+        .filter_map(|tag| window.rfind(tag).map(|byte_pos| (byte_pos, tag)))
+        .max_by_key(|(byte_pos, _)| *byte_pos)
+        .map(|(byte_pos, tag)| start + window[..byte_pos + tag.len()].chars().count())
+}
@@ -0,0 +1,149 @@
+use crate::tika::wrappers::ConfigValue;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Mirrors the Java `org.apache.tika.parser.pdf.PDFParserConfig$OCR_STRATEGY`
+/// enum; [`JPDFParserConfig::new`](crate::tika::wrappers::JPDFParserConfig::new)
+/// sends `to_string()` of whichever variant is set across as the setter's
+/// string argument, so the `Display` names below must match the Java enum
+/// names exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PdfOcrStrategy {
+    #[default]
+    Auto,
+    NoOcr,
+    OcrOnly,
+    OcrAndTextExtraction,
+}
+
+impl fmt::Display for PdfOcrStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PdfOcrStrategy::Auto => "AUTO",
+            PdfOcrStrategy::NoOcr => "NO_OCR",
+            PdfOcrStrategy::OcrOnly => "OCR_ONLY",
+            PdfOcrStrategy::OcrAndTextExtraction => "OCR_AND_TEXT_EXTRACTION",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Rust-side mirror of `org.apache.tika.parser.pdf.PDFParserConfig`, passed to
+/// [`JPDFParserConfig::new`](crate::tika::wrappers::JPDFParserConfig::new) to
+/// build the Java object the native PDF parser call actually uses.
+#[derive(Debug, Clone)]
+pub struct PdfParserConfig {
+    pub extract_inline_images: bool,
+    pub extract_unique_inline_images_only: bool,
+    pub extract_marked_content: bool,
+    pub extract_annotation_text: bool,
+    pub ocr_strategy: PdfOcrStrategy,
+    /// Reflective passthrough for Tika PDF setters this struct hasn't grown a
+    /// typed field for yet - see
+    /// [`apply_extra_config`](crate::tika::wrappers) for how it's applied.
+    pub extra: HashMap<String, ConfigValue>,
+}
+
+impl Default for PdfParserConfig {
+    fn default() -> Self {
+        Self {
+            extract_inline_images: true,
+            extract_unique_inline_images_only: false,
+            extract_marked_content: false,
+            extract_annotation_text: false,
+            ocr_strategy: PdfOcrStrategy::default(),
+            extra: HashMap::new(),
+        }
+    }
+}
+
+/// Rust-side mirror of `org.apache.tika.parser.microsoft.OfficeParserConfig`,
+/// passed to
+/// [`JOfficeParserConfig::new`](crate::tika::wrappers::JOfficeParserConfig::new).
+#[derive(Debug, Clone)]
+pub struct OfficeParserConfig {
+    pub extract_macros: bool,
+    pub include_deleted_content: bool,
+    pub include_move_from_content: bool,
+    pub include_shape_based_content: bool,
+    pub include_headers_and_footers: bool,
+    pub include_missing_rows: bool,
+    pub include_slide_notes: bool,
+    pub include_slide_master_content: bool,
+    pub concatenate_phonetic_runs: bool,
+    pub extract_all_alternatives_from_msg: bool,
+    /// Reflective passthrough, same as [`PdfParserConfig::extra`].
+    pub extra: HashMap<String, ConfigValue>,
+}
+
+impl Default for OfficeParserConfig {
+    fn default() -> Self {
+        Self {
+            extract_macros: false,
+            include_deleted_content: false,
+            include_move_from_content: false,
+            include_shape_based_content: true,
+            include_headers_and_footers: true,
+            include_missing_rows: false,
+            include_slide_notes: true,
+            include_slide_master_content: true,
+            concatenate_phonetic_runs: true,
+            extract_all_alternatives_from_msg: false,
+            extra: HashMap::new(),
+        }
+    }
+}
+
+/// Rust-side mirror of `org.apache.tika.parser.ocr.TesseractOCRConfig`, passed
+/// to
+/// [`JTesseractOcrConfig::new`](crate::tika::wrappers::JTesseractOcrConfig::new).
+#[derive(Debug, Clone)]
+pub struct TesseractOcrConfig {
+    pub density: i32,
+    pub depth: i32,
+    pub timeout_seconds: i32,
+    pub enable_image_preprocessing: bool,
+    pub apply_rotation: bool,
+    /// Tesseract language pack code(s) to use, `+`-joined for more than one
+    /// (e.g. `"eng+deu"`) - see
+    /// [`validate_tesseract_languages`](crate::tika::wrappers).
+    pub language: String,
+    /// Tesseract page segmentation mode (`--psm`), e.g. `3` for fully
+    /// automatic layout analysis.
+    pub page_seg_mode: i32,
+    /// Tesseract OCR engine mode (`--oem`), e.g. `3` for the default LSTM +
+    /// legacy combination.
+    pub ocr_engine_mode: i32,
+    /// Files smaller than this are skipped for OCR.
+    pub min_file_size_bytes: i64,
+    /// Files larger than this are skipped for OCR.
+    pub max_file_size_bytes: i64,
+    /// Percentage to resize images to before OCR (Tika's `setResize`), e.g.
+    /// `200` to double the size for small/low-DPI scans.
+    pub resize_percent: i32,
+    /// Restricts recognized output to this character set when set, passed to
+    /// Tesseract's `setCharacterWhitelist`.
+    pub character_whitelist: Option<String>,
+    /// Reflective passthrough, same as [`PdfParserConfig::extra`].
+    pub extra: HashMap<String, ConfigValue>,
+}
+
+impl Default for TesseractOcrConfig {
+    fn default() -> Self {
+        Self {
+            density: 300,
+            depth: 4,
+            timeout_seconds: 120,
+            enable_image_preprocessing: false,
+            apply_rotation: false,
+            language: "eng".to_string(),
+            page_seg_mode: 3,
+            ocr_engine_mode: 3,
+            min_file_size_bytes: 0,
+            max_file_size_bytes: i64::MAX,
+            resize_percent: 0,
+            character_whitelist: None,
+            extra: HashMap::new(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::cfb::{CompoundFile, CFB_MAGIC};
 use crate::errors::{Error, ExtractResult};
+use crate::zip_sniff::{sniff_ooxml_subtype, ZIP_MAGIC};
 use std::collections::HashMap;
 
 /// Represents an embedded document extracted from a container file
@@ -12,17 +14,106 @@ pub struct EmbeddedDocument {
     pub content: Vec<u8>,
     /// Optional relationship ID (for formats like OOXML)
     pub embedded_relationship_id: Option<String>,
+    /// Embedded documents found recursively inside this one (e.g. a spreadsheet
+    /// embedded inside a Word doc embedded inside a PowerPoint deck). Only
+    /// populated when extraction was requested with a recursive mode; empty
+    /// otherwise.
+    pub children: Vec<EmbeddedDocument>,
+    /// How many containers deep this document was found; `0` for a document
+    /// extracted directly from the top-level file. Only meaningful when
+    /// extraction was requested with a recursive mode.
+    pub depth: usize,
+    /// The embedding path of this document's parent, so callers can reconstruct
+    /// the tree without relying on `children`. `None` for a top-level document.
+    /// `extract_embedded_optimized_recursive` sets it to the parent's worklist
+    /// path (e.g. `"2"`, or `"2/0"` for a document nested two levels deep);
+    /// `extract_embedded_from_file_recursive` sets it to the parent's
+    /// `resource_name`.
+    pub parent_path: Option<String>,
+    /// A content-addressed digest of `content`, used to key the deduplicated
+    /// blob table in the optimized packed wire format (see
+    /// `parse_embedded_optimized.rs`) and available here so downstream code can
+    /// dedupe identical embedded blobs (e.g. a logo repeated across a
+    /// presentation) in its own store too.
+    pub content_hash: [u8; 32],
 }
 
 /// Result of embedded document extraction
 #[derive(Debug)]
 pub struct EmbeddedExtractResult {
-    /// List of extracted embedded documents
+    /// List of successfully extracted embedded documents
     pub documents: Vec<EmbeddedDocument>,
+    /// Embedded documents that failed to extract, reported instead of aborting
+    /// the whole call so one corrupt or encrypted attachment doesn't discard
+    /// every sibling that parsed fine.
+    pub errors: Vec<EmbeddedDocumentError>,
     /// Metadata from the parent document
     pub metadata: HashMap<String, Vec<String>>,
 }
 
+/// Tika's broad fault categories for a single embedded document, mapped from
+/// the numeric code the Java side reports so callers can match on it instead
+/// of string-sniffing `message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddedDocumentErrorKind {
+    /// The document is password-protected and couldn't be decrypted with the
+    /// credentials (if any) Tika was configured with.
+    EncryptedDocument,
+    /// Tika has no parser registered for this document's content type.
+    UnsupportedFormat,
+    /// The document's container structure (OLE2, ZIP, etc.) is malformed or
+    /// truncated.
+    CorruptContainer,
+    /// Any other failure raised while parsing this document.
+    RuntimeFault,
+}
+
+impl EmbeddedDocumentErrorKind {
+    /// Maps the numeric fault category the packed format and
+    /// `JEmbeddedDocument` carry to its typed `EmbeddedDocumentErrorKind`.
+    /// An unrecognized code falls back to `RuntimeFault` rather than
+    /// panicking, since the Java side may report categories this crate
+    /// doesn't know about yet.
+    pub(crate) fn from_code(code: i32) -> Self {
+        match code {
+            1 => Self::EncryptedDocument,
+            2 => Self::UnsupportedFormat,
+            3 => Self::CorruptContainer,
+            _ => Self::RuntimeFault,
+        }
+    }
+}
+
+/// A single embedded document that failed to extract, reported alongside any
+/// sibling documents that succeeded (see [`EmbeddedExtractResult::errors`])
+/// rather than discarding them all over one bad attachment.
+#[derive(Debug, Clone)]
+pub struct EmbeddedDocumentError {
+    /// The name/path of the embedded resource that failed, as reported by Tika.
+    pub resource_name: String,
+    /// Optional relationship ID (for formats like OOXML), when Tika could
+    /// still determine one despite the failure.
+    pub embedded_relationship_id: Option<String>,
+    /// The broad category of failure.
+    pub kind: EmbeddedDocumentErrorKind,
+    /// The underlying Tika/Java-side cause text. This crosses the JNI
+    /// boundary as a string rather than a structured exception, so it's kept
+    /// verbatim here instead of being wrapped in another error type.
+    pub message: String,
+}
+
+impl std::fmt::Display for EmbeddedDocumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "embedded document \"{}\" ({:?}): {}",
+            self.resource_name, self.kind, self.message
+        )
+    }
+}
+
+impl std::error::Error for EmbeddedDocumentError {}
+
 impl EmbeddedDocument {
     /// Save the embedded document to a file
     pub fn save_to_file(&self, path: &str) -> std::io::Result<()> {
@@ -41,7 +132,8 @@ impl EmbeddedDocument {
     pub fn size(&self) -> usize {
         self.content.len()
     }
-    
+
+
     /// Check if this is likely an image based on content type
     pub fn is_image(&self) -> bool {
         self.content_type.starts_with("image/")
@@ -59,9 +151,223 @@ impl EmbeddedDocument {
             "application/vnd.openxmlformats-officedocument.presentationml.presentation"
         ) || self.content_type.starts_with("text/")
     }
+
+    /// Unwraps an OLE2 compound-file wrapper around this embedded document to recover
+    /// the real native payload (e.g. the actual `.xlsx` behind a legacy Excel
+    /// embedding), mirroring how Office round-trips embedded spreadsheets and
+    /// presentations through a `Package` stream inside an OLE container.
+    ///
+    /// Returns `Ok(None)` when `content` isn't CFB-framed, or doesn't look like an
+    /// OLE object wrapper (no `\x01Ole` stream) or carries no `Package` stream to
+    /// unwrap.
+    pub fn unwrap_ole(&self) -> ExtractResult<Option<EmbeddedDocument>> {
+        let Some(cfb) = CompoundFile::parse(&self.content)? else {
+            return Ok(None);
+        };
+
+        // The presence of a "\x01Ole" stream is what marks this as an OLE object
+        // wrapper rather than some other kind of compound file.
+        if cfb.stream("\u{1}Ole").is_none() {
+            return Ok(None);
+        }
+
+        let Some(package) = cfb.stream("Package") else {
+            return Ok(None);
+        };
+
+        let prog_id = cfb.stream("\u{1}CompObj").and_then(read_comp_obj_prog_id);
+        let content_type = prog_id
+            .as_deref()
+            .and_then(prog_id_to_content_type)
+            .unwrap_or_else(|| self.content_type.clone());
+
+        Ok(Some(EmbeddedDocument {
+            resource_name: self.resource_name.clone(),
+            content_type,
+            content_hash: hash_content(&package),
+            content: package.to_vec(),
+            embedded_relationship_id: self.embedded_relationship_id.clone(),
+            children: Vec::new(),
+            depth: self.depth,
+            parent_path: self.parent_path.clone(),
+        }))
+    }
+
+    /// Whether this embedded document is itself a container format that may carry
+    /// further embedded documents. Used to decide whether recursive extraction
+    /// should descend into it.
+    pub fn is_container(&self) -> bool {
+        matches!(self.content_type.as_str(),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" |
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" |
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation" |
+            "application/msword" |
+            "application/vnd.ms-excel" |
+            "application/vnd.ms-powerpoint" |
+            "application/zip" |
+            "application/x-tika-ooxml" |
+            "application/x-tika-msoffice"
+        )
+    }
+
+    /// Recovers the document recursive extraction should actually test with
+    /// [`Self::is_container`] and recurse into, for documents Tika could only
+    /// tag generically (`application/octet-stream`, `application/x-tika-msoffice`,
+    /// ...) - exactly the case [`Self::detect_content_type`] and [`Self::unwrap_ole`]
+    /// exist to recover from, but which recursive callers previously checked
+    /// `is_container` without ever calling. Tries `unwrap_ole` first, since an
+    /// OLE2-wrapped document's own content type is rarely the interesting one -
+    /// the unwrapped payload's is; falls back to `detect_content_type` for
+    /// containers that aren't OLE-wrapped but were still tagged generically.
+    /// Returns a clone of `self` unchanged when neither recovers anything, so
+    /// callers can always recurse against the returned document rather than
+    /// branching on an `Option`.
+    pub(crate) fn refine_for_recursion(&self) -> EmbeddedDocument {
+        if let Ok(Some(unwrapped)) = self.unwrap_ole() {
+            return unwrapped;
+        }
+
+        if GENERIC_CONTENT_TYPES.contains(&self.content_type.as_str()) {
+            if let Some(detected) = self.detect_content_type() {
+                let mut refined = self.clone();
+                refined.content_type = detected;
+                return refined;
+            }
+        }
+
+        self.clone()
+    }
+
+    /// Sniffs `content`'s leading bytes to recover a precise MIME type for cases
+    /// where Tika could only tag this embedded resource with something generic like
+    /// `application/octet-stream` or `application/x-tika-msoffice`.
+    ///
+    /// Shared CFB containers are disambiguated by which legacy Office stream they
+    /// carry (`WordDocument`, `Workbook`/`Book`, `PowerPoint Document`); ZIP
+    /// containers are disambiguated by which OOXML part they carry.
+    pub fn detect_content_type(&self) -> Option<String> {
+        if self.content.starts_with(&CFB_MAGIC) {
+            let cfb = CompoundFile::parse(&self.content).ok().flatten()?;
+            if cfb.stream("WordDocument").is_some() {
+                return Some("application/msword".to_string());
+            }
+            if cfb.stream("Workbook").is_some() || cfb.stream("Book").is_some() {
+                return Some("application/vnd.ms-excel".to_string());
+            }
+            if cfb.stream("PowerPoint Document").is_some() {
+                return Some("application/vnd.ms-powerpoint".to_string());
+            }
+            return None;
+        }
+
+        if self.content.starts_with(&ZIP_MAGIC) {
+            return sniff_ooxml_subtype(&self.content).map(str::to_string);
+        }
+
+        None
+    }
+}
+
+/// Content types generic enough that Tika itself couldn't pin down the real
+/// format - e.g. it only recognized an OLE2 or ZIP container shape, not what's
+/// inside. Shared between [`EmbeddedExtractResult::refine_content_types`] and
+/// [`EmbeddedDocument::refine_for_recursion`], both of which fall back to
+/// sniffing raw bytes for exactly these types.
+const GENERIC_CONTENT_TYPES: &[&str] = &[
+    "",
+    "application/octet-stream",
+    "application/x-tika-msoffice",
+    "application/x-tika-ooxml",
+];
+
+/// Computes a 32-byte content-addressed digest of `content`, used to key the
+/// deduplicated blob table in the optimized packed wire format and to populate
+/// `EmbeddedDocument::content_hash` elsewhere. Since two different documents
+/// colliding here means one silently hands out the other's bytes from the
+/// blob table, this needs real collision resistance, not just a fast
+/// fingerprint - hence blake3 rather than a `Hash`/`Hasher` construction.
+pub(crate) fn hash_content(content: &[u8]) -> [u8; 32] {
+    blake3::hash(content).into()
+}
+
+/// Reads the length-prefixed ANSI progId string out of the tail of a `\x01CompObj`
+/// stream (e.g. `"Excel.Sheet.12"`). The fixed header is 4 bytes reserved, 4 bytes
+/// version, and 20 bytes of reserved CLSID, followed by the length-prefixed
+/// "AnsiUserType" display name, the "AnsiClipboardFormat" field, and then the
+/// length-prefixed string holding the progId.
+fn read_comp_obj_prog_id(comp_obj: &[u8]) -> Option<String> {
+    let read_len = |pos: usize| -> Option<usize> {
+        Some(u32::from_le_bytes(comp_obj.get(pos..pos + 4)?.try_into().ok()?) as usize)
+    };
+    // Skip the AnsiUserType display name (e.g. "Microsoft Excel Worksheet").
+    let mut pos = 32 + read_len(28)?;
+    // AnsiClipboardFormat: a 0 marker means absent, 0xFFFFFFFE/0xFFFFFFFF
+    // precede a 4-byte standard format id, anything else is a string length.
+    pos += 4 + match read_len(pos)? {
+        0 => 0,
+        0xFFFF_FFFE | 0xFFFF_FFFF => 4,
+        len => len,
+    };
+    let len = read_len(pos)?;
+    let bytes = comp_obj.get(pos + 4..pos + 4 + len)?;
+    let trimmed = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    Some(String::from_utf8_lossy(trimmed).into_owned())
+}
+
+/// Maps a CompObj progId (e.g. `"Excel.Sheet.12"`) to the MIME type of the file it
+/// identifies.
+///
+/// The trailing version number matters, not just the prefix: `.12` progIds
+/// (`Excel.Sheet.12`, `Word.Document.12`, `PowerPoint.Show.12`) are Office
+/// 2007+'s OOXML formats - still ZIP packages under the hood even when wrapped
+/// in an OLE2 `Package` stream - while earlier versions (`.8` or no suffix at
+/// all) are the legacy binary formats. Mapping both to the legacy MIME type
+/// would save a real `.xlsx`'s bytes under a `.xls` filename downstream.
+fn prog_id_to_content_type(prog_id: &str) -> Option<String> {
+    let prog_id = prog_id.to_ascii_lowercase();
+    let is_ooxml = prog_id
+        .rsplit('.')
+        .next()
+        .is_some_and(|suffix| suffix.parse::<u32>().is_ok_and(|version| version >= 12));
+
+    if prog_id.starts_with("excel.sheet") {
+        Some(if is_ooxml {
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string()
+        } else {
+            "application/vnd.ms-excel".to_string()
+        })
+    } else if prog_id.starts_with("powerpoint") {
+        Some(if is_ooxml {
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation".to_string()
+        } else {
+            "application/vnd.ms-powerpoint".to_string()
+        })
+    } else if prog_id.starts_with("word.document") {
+        Some(if is_ooxml {
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document".to_string()
+        } else {
+            "application/msword".to_string()
+        })
+    } else {
+        None
+    }
 }
 
 impl EmbeddedExtractResult {
+    /// Overwrites blank or generic `content_type`s in place with whatever
+    /// [`EmbeddedDocument::detect_content_type`] can recover by sniffing the
+    /// content's bytes, so `save_all_to_directory` writes files with correct
+    /// extensions. This is opt-in since sniffing every document costs time.
+    pub fn refine_content_types(&mut self) {
+        for doc in &mut self.documents {
+            if GENERIC_CONTENT_TYPES.contains(&doc.content_type.as_str()) {
+                if let Some(detected) = doc.detect_content_type() {
+                    doc.content_type = detected;
+                }
+            }
+        }
+    }
+
     /// Get only image documents
     pub fn images(&self) -> Vec<&EmbeddedDocument> {
         self.documents.iter()
@@ -83,27 +389,253 @@ impl EmbeddedExtractResult {
             .sum()
     }
     
-    /// Save all embedded documents to a directory
+    /// Save all embedded documents to a directory. Documents with recursively
+    /// extracted `children` are laid out as nested folders named after their
+    /// parent resource, so a tree structure on disk mirrors the container nesting.
     pub fn save_all_to_directory(&self, base_dir: &str) -> ExtractResult<()> {
         use std::fs;
         use std::path::Path;
-        
+
         // Create base directory
         fs::create_dir_all(base_dir)
             .map_err(|e| Error::IoError(e.to_string()))?;
-        
+
         for (index, doc) in self.documents.iter().enumerate() {
-            let filename = if doc.resource_name.is_empty() {
-                format!("embedded_{}", index)
-            } else {
-                doc.resource_name.clone()
-            };
-            
-            let file_path = Path::new(base_dir).join(&filename);
-            doc.save_to_file(file_path.to_str().unwrap())
+            save_document_tree(doc, index, Path::new(base_dir))?;
+        }
+
+        Ok(())
+    }
+
+    /// Deduplicates documents by content hash, collapsing byte-identical payloads
+    /// (e.g. the same logo embedded dozens of times) into one entry while keeping
+    /// every original `resource_name` as an alias of it.
+    pub fn dedup(&self) -> DedupedEmbeddedDocuments {
+        let mut by_hash: HashMap<[u8; 32], DedupedDocument> = HashMap::new();
+        let mut order = Vec::new();
+
+        for doc in &self.documents {
+            let hash = doc.content_hash;
+            by_hash
+                .entry(hash)
+                .and_modify(|deduped| deduped.aliases.push(doc.resource_name.clone()))
+                .or_insert_with(|| {
+                    order.push(hash);
+                    DedupedDocument {
+                        content_hash: hash,
+                        content_type: doc.content_type.clone(),
+                        content: doc.content.clone(),
+                        aliases: vec![doc.resource_name.clone()],
+                        embedded_relationship_id: doc.embedded_relationship_id.clone(),
+                    }
+                });
+        }
+
+        DedupedEmbeddedDocuments {
+            documents: order
+                .into_iter()
+                .map(|hash| by_hash.remove(&hash).unwrap())
+                .collect(),
+        }
+    }
+
+    /// Writes each unique content blob (see [`Self::dedup`]) to `base_dir` exactly
+    /// once, alongside a `manifest.json` array of `{resource_name, file,
+    /// relationship_id}` entries (one per alias, not deduplicated by name) so
+    /// callers can reconstruct the original layout without duplicating bytes
+    /// on disk.
+    pub fn save_all_to_directory_dedup(&self, base_dir: &str) -> ExtractResult<()> {
+        use std::fs;
+        use std::path::Path;
+
+        fs::create_dir_all(base_dir).map_err(|e| Error::IoError(e.to_string()))?;
+
+        let deduped = self.dedup();
+        let mut manifest_entries = Vec::new();
+
+        for doc in &deduped.documents {
+            let filename = format!("{}.bin", hex_encode(&doc.content_hash));
+            fs::write(Path::new(base_dir).join(&filename), &doc.content)
                 .map_err(|e| Error::IoError(e.to_string()))?;
+
+            for alias in &doc.aliases {
+                manifest_entries.push((
+                    alias.clone(),
+                    filename.clone(),
+                    doc.embedded_relationship_id.clone(),
+                ));
+            }
         }
-        
+
+        fs::write(
+            Path::new(base_dir).join("manifest.json"),
+            build_manifest_json(&manifest_entries),
+        )
+        .map_err(|e| Error::IoError(e.to_string()))?;
+
         Ok(())
     }
+}
+
+/// A deduplicated view over an [`EmbeddedExtractResult`]: documents with identical
+/// content are collapsed into one entry, keeping every original `resource_name` as
+/// an alias. Produced by [`EmbeddedExtractResult::dedup`].
+#[derive(Debug)]
+pub struct DedupedEmbeddedDocuments {
+    /// One entry per unique content hash.
+    pub documents: Vec<DedupedDocument>,
+}
+
+/// One unique content blob from a [`DedupedEmbeddedDocuments`], with every resource
+/// name that originally carried this content kept as an alias.
+#[derive(Debug, Clone)]
+pub struct DedupedDocument {
+    pub content_hash: [u8; 32],
+    pub content_type: String,
+    pub content: Vec<u8>,
+    /// Every original `resource_name` that carried this exact content.
+    pub aliases: Vec<String>,
+    pub embedded_relationship_id: Option<String>,
+}
+
+/// Hand-rolls the small, fixed-shape manifest JSON produced by
+/// [`EmbeddedExtractResult::save_all_to_directory_dedup`]
+/// (`[{"resource_name": ..., "file": ..., "relationship_id": ...}, ...]`), to
+/// avoid pulling in a JSON dependency for one document shape.
+///
+/// This is a JSON array rather than an object keyed by `resource_name`:
+/// Office commonly reuses generic names (`image1.png`) across unrelated
+/// parts, and two aliases of genuinely different content hashes that happen
+/// to share a `resource_name` would otherwise collide on the same object key,
+/// silently losing one.
+fn build_manifest_json(entries: &[(String, String, Option<String>)]) -> String {
+    let mut out = String::from("[\n");
+    for (i, (resource_name, file, relationship_id)) in entries.iter().enumerate() {
+        out.push_str("  {\n");
+        out.push_str(&format!(
+            "    \"resource_name\": \"{}\",\n",
+            json_escape(resource_name)
+        ));
+        out.push_str(&format!("    \"file\": \"{}\",\n", json_escape(file)));
+        match relationship_id {
+            Some(id) => out.push_str(&format!("    \"relationship_id\": \"{}\"\n", json_escape(id))),
+            None => out.push_str("    \"relationship_id\": null\n"),
+        }
+        out.push_str(if i + 1 < entries.len() { "  },\n" } else { "  }\n" });
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Escapes `s` for embedding in a JSON string literal: backslashes, quotes,
+/// and the control characters Tika can surface from a malformed container
+/// (e.g. a raw newline/tab in a resource name), which would otherwise produce
+/// invalid JSON.
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Renders a content hash as lowercase hex, e.g. for use as a deduped blob's
+/// filename.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Writes `doc` under `dir`, then recurses its `children` into a `<filename>_embedded`
+/// subfolder so nested containers produce nested folders on disk.
+fn save_document_tree(doc: &EmbeddedDocument, index: usize, dir: &std::path::Path) -> ExtractResult<()> {
+    let filename = resolve_filename(doc, index);
+
+    let file_path = dir.join(&filename);
+    doc.save_to_file(file_path.to_str().unwrap())
+        .map_err(|e| Error::IoError(e.to_string()))?;
+
+    if !doc.children.is_empty() {
+        let children_dir = dir.join(format!("{}_embedded", filename));
+        std::fs::create_dir_all(&children_dir).map_err(|e| Error::IoError(e.to_string()))?;
+        for (child_index, child) in doc.children.iter().enumerate() {
+            save_document_tree(child, child_index, &children_dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Works out the filename to save `doc` as: falls back to its relationship id (or a
+/// positional name) when `resource_name` is empty, sanitizes path separators and
+/// `..` segments so embedded paths can't escape the save directory, and appends the
+/// extension implied by `content_type` when the name doesn't already have one (the
+/// real-world case of an embedded spreadsheet arriving as an extensionless `.bin`).
+fn resolve_filename(doc: &EmbeddedDocument, index: usize) -> String {
+    let base = if !doc.resource_name.is_empty() {
+        sanitize_resource_name(&doc.resource_name)
+    } else if let Some(rel_id) = &doc.embedded_relationship_id {
+        sanitize_resource_name(rel_id)
+    } else {
+        format!("embedded_{}", index)
+    };
+
+    if has_extension(&base) {
+        return base;
+    }
+
+    match content_type_to_extension(&doc.content_type) {
+        Some(ext) => format!("{}.{}", base, ext),
+        None => base,
+    }
+}
+
+/// Strips path separators and `.`/`..` segments from an embedded resource name so a
+/// malicious or malformed name can't escape the save directory.
+///
+/// `pub(crate)` so [`crate::tika::parse_embedded_to_dir`] can reuse the same
+/// sanitization when naming files it streams straight to disk.
+pub(crate) fn sanitize_resource_name(name: &str) -> String {
+    name.replace('\\', "/")
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub(crate) fn has_extension(name: &str) -> bool {
+    std::path::Path::new(name).extension().is_some()
+}
+
+/// Maps a MIME type to the file extension Office/readers expect, used to fix up
+/// embedded documents whose `resource_name` lacks one.
+///
+/// `pub(crate)` so [`crate::tika::parse_embedded_to_dir`] can reuse it when naming
+/// files it streams straight to disk.
+pub(crate) fn content_type_to_extension(content_type: &str) -> Option<&'static str> {
+    Some(match content_type {
+        "application/pdf" => "pdf",
+        "application/msword" => "doc",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => "docx",
+        "application/vnd.ms-excel" => "xls",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => "xlsx",
+        "application/vnd.ms-powerpoint" => "ppt",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation" => "pptx",
+        "application/zip" => "zip",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/bmp" => "bmp",
+        "image/tiff" => "tiff",
+        "text/plain" => "txt",
+        "text/html" => "html",
+        _ => return None,
+    })
 }
\ No newline at end of file
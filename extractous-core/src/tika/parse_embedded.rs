@@ -1,17 +1,62 @@
-use crate::embedded::{EmbeddedDocument, EmbeddedExtractResult};
+use crate::embedded::{
+    hash_content, EmbeddedDocument, EmbeddedDocumentError, EmbeddedDocumentErrorKind,
+    EmbeddedExtractResult,
+};
 use crate::errors::{Error, ExtractResult};
 use crate::tika::jni_utils::{jni_call_static_method, jni_new_string_as_jvalue};
 use crate::tika::vm;
-use crate::tika::wrappers::{JEmbeddedExtractResult, JOfficeParserConfig, JPDFParserConfig, JTesseractOcrConfig};
+use crate::tika::wrappers::{
+    JEmbeddedDocument, JEmbeddedExtractResult, JOfficeParserConfig, JPDFParserConfig,
+    JTesseractOcrConfig,
+};
 use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
 use jni::objects::{JValue};
 use jni::{AttachGuard};
+use std::collections::HashMap;
 
 fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>> {
     let env = vm().attach_current_thread()?;
     Ok(env)
 }
 
+/// Splits the Java side's flat document list into the ones that extracted
+/// successfully and the ones that failed individually (`error_code != 0`),
+/// so one bad attachment doesn't take down its siblings.
+fn partition_documents(
+    j_docs: Vec<JEmbeddedDocument>,
+) -> (Vec<EmbeddedDocument>, Vec<EmbeddedDocumentError>) {
+    let mut documents = Vec::with_capacity(j_docs.len());
+    let mut errors = Vec::new();
+
+    for j_doc in j_docs {
+        if j_doc.error_code != 0 {
+            errors.push(EmbeddedDocumentError {
+                resource_name: j_doc.resource_name,
+                embedded_relationship_id: j_doc.embedded_relationship_id,
+                kind: EmbeddedDocumentErrorKind::from_code(j_doc.error_code),
+                message: j_doc.error_message.unwrap_or_else(|| {
+                    format!("Embedded document extraction failed with code {}", j_doc.error_code)
+                }),
+            });
+            continue;
+        }
+
+        let content_hash = hash_content(&j_doc.content);
+        documents.push(EmbeddedDocument {
+            resource_name: j_doc.resource_name,
+            content_type: j_doc.content_type,
+            content: j_doc.content,
+            embedded_relationship_id: j_doc.embedded_relationship_id,
+            children: Vec::new(),
+            depth: 0,
+            parent_path: None,
+            content_hash,
+        });
+    }
+
+    (documents, errors)
+}
+
 /// Extract embedded documents from a file
 pub fn extract_embedded_from_file(
     file_path: &str,
@@ -59,23 +104,17 @@ pub fn extract_embedded_from_file(
         });
         return Err(Error::ParseError(error_msg));
     }
-    
-    // Convert Java documents to Rust documents
-    let mut documents = Vec::with_capacity(j_result.documents.len());
-    for j_doc in j_result.documents {
-        documents.push(EmbeddedDocument {
-            resource_name: j_doc.resource_name,
-            content_type: j_doc.content_type,
-            content: j_doc.content,
-            embedded_relationship_id: j_doc.embedded_relationship_id,
-        });
-    }
-    
+
+    // Convert Java documents to Rust documents, separating out any that
+    // failed to extract individually rather than discarding their siblings.
+    let (documents, errors) = partition_documents(j_result.documents);
+
     // Use the metadata from j_result
     let metadata = j_result.metadata;
-    
+
     Ok(EmbeddedExtractResult {
         documents,
+        errors,
         metadata,
     })
 }
@@ -131,23 +170,186 @@ pub fn extract_embedded_from_bytes(
         });
         return Err(Error::ParseError(error_msg));
     }
-    
-    // Convert Java documents to Rust documents
-    let mut documents = Vec::with_capacity(j_result.documents.len());
-    for j_doc in j_result.documents {
-        documents.push(EmbeddedDocument {
-            resource_name: j_doc.resource_name,
-            content_type: j_doc.content_type,
-            content: j_doc.content,
-            embedded_relationship_id: j_doc.embedded_relationship_id,
-        });
-    }
-    
+
+    // Convert Java documents to Rust documents, separating out any that
+    // failed to extract individually rather than discarding their siblings.
+    let (documents, errors) = partition_documents(j_result.documents);
+
     // Use the metadata from j_result
     let metadata = j_result.metadata;
-    
+
     Ok(EmbeddedExtractResult {
         documents,
+        errors,
         metadata,
     })
-}
\ No newline at end of file
+}
+/// Default recursion limit for [`extract_embedded_from_file_recursive`], bounding
+/// how many container-in-container levels are descended into.
+pub const DEFAULT_MAX_EMBEDDED_DEPTH: usize = 5;
+
+/// Like [`extract_embedded_from_file`], but re-feeds any embedded document whose
+/// detected type is itself a container (e.g. a `.docx` embedded in a `.pptx`) back
+/// through the extractor, populating each document's `children` to produce a tree.
+/// `max_depth` guards against cycles and zip-bomb-style blowups from
+/// self-referential or deeply nested containers.
+pub fn extract_embedded_from_file_recursive(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    max_depth: usize,
+) -> ExtractResult<EmbeddedExtractResult> {
+    let mut result = extract_embedded_from_file(file_path, pdf_conf, office_conf, ocr_conf)?;
+    for doc in &mut result.documents {
+        recurse_into_embedded(doc, pdf_conf, office_conf, ocr_conf, max_depth)?;
+    }
+    Ok(result)
+}
+
+/// Recursively descends into `doc` if it's a container, attaching each nested
+/// embedded document as a child and recursing further until `remaining_depth`
+/// reaches zero.
+fn recurse_into_embedded(
+    doc: &mut EmbeddedDocument,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    remaining_depth: usize,
+) -> ExtractResult<()> {
+    if remaining_depth == 0 {
+        return Ok(());
+    }
+
+    // Tika frequently tags embedded containers generically (e.g.
+    // `application/octet-stream`, `application/x-tika-msoffice`); refine
+    // before testing `is_container`, or a container tagged that way is
+    // silently treated as a leaf and never descended into.
+    let candidate = doc.refine_for_recursion();
+    if !candidate.is_container() {
+        return Ok(());
+    }
+
+    // A document that merely looks like a container (e.g. a misdetected type)
+    // shouldn't fail the whole tree - leave it as a leaf in that case.
+    let nested = match extract_embedded_from_bytes(&candidate.content, pdf_conf, office_conf, ocr_conf) {
+        Ok(nested) => nested,
+        Err(_) => return Ok(()),
+    };
+
+    for mut child in nested.documents {
+        child.depth = doc.depth + 1;
+        child.parent_path = Some(doc.resource_name.clone());
+        recurse_into_embedded(&mut child, pdf_conf, office_conf, ocr_conf, remaining_depth - 1)?;
+        doc.children.push(child);
+    }
+
+    Ok(())
+}
+
+/// An embedded document recovered via [`extract_embedded_recursive_with_metadata`],
+/// carrying its own extraction metadata plus its position in the embedding tree.
+#[derive(Debug, Clone)]
+pub struct RecursiveEmbeddedDocument {
+    pub resource_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+    pub embedded_relationship_id: Option<String>,
+    /// This document's own Tika extraction metadata (empty for documents Tika
+    /// couldn't parse further, e.g. plain images).
+    pub metadata: HashMap<String, Vec<String>>,
+    /// How many containers deep this document was found (0 = top-level).
+    pub depth: usize,
+    /// Resource path of the parent document this was embedded in, if any.
+    pub parent_path: Option<String>,
+}
+
+/// Result of [`extract_embedded_recursive_with_metadata`]: every embedded document
+/// found while recursively re-parsing containers, flattened into one list. Use
+/// `depth`/`parent_path` on each entry to reconstruct the tree.
+#[derive(Debug)]
+pub struct RecursiveEmbeddedExtractResult {
+    pub documents: Vec<RecursiveEmbeddedDocument>,
+    pub metadata: HashMap<String, Vec<String>>,
+}
+
+/// Default cap on how many containers deep recursive extraction will descend.
+pub const DEFAULT_MAX_RECURSIVE_DEPTH: i32 = 5;
+
+/// Default cap, in bytes, on any single embedded document's size during recursive
+/// extraction, to avoid zip-bomb-style blowups from self-referential or
+/// maliciously crafted containers.
+pub const DEFAULT_MAX_EMBEDDED_DOCUMENT_SIZE: i64 = 100 * 1024 * 1024;
+
+/// Recursively re-parses every embedded document's bytes back through Tika (e.g. a
+/// ZIP-in-email-in-PDF) via Tika's own recursive parser wrapper, producing a
+/// flattened tree of embedded documents each carrying their own metadata, embed
+/// depth, and parent resource path. `max_depth` and `max_doc_size` guard against
+/// cycles and zip-bomb-style blowups.
+pub fn extract_embedded_recursive_with_metadata(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    max_depth: i32,
+    max_doc_size: i64,
+) -> ExtractResult<RecursiveEmbeddedExtractResult> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+
+    let result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractEmbeddedRecursive",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        IJ\
+        )Lai/yobix/EmbeddedExtractResult;",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            JValue::Int(max_depth),
+            JValue::Long(max_doc_size),
+        ],
+    )?;
+
+    let result_obj = result.l()?;
+    let j_result = JEmbeddedExtractResult::new(&mut env, result_obj)?;
+
+    if j_result.error_code != 0 {
+        let error_msg = j_result.error_message.unwrap_or_else(|| {
+            format!(
+                "Recursive embedded extraction failed with code {}",
+                j_result.error_code
+            )
+        });
+        return Err(Error::ParseError(error_msg));
+    }
+
+    let documents = j_result
+        .documents
+        .into_iter()
+        .map(|j_doc| RecursiveEmbeddedDocument {
+            resource_name: j_doc.resource_name,
+            content_type: j_doc.content_type,
+            content: j_doc.content,
+            embedded_relationship_id: j_doc.embedded_relationship_id,
+            metadata: j_doc.metadata,
+            depth: j_doc.depth,
+            parent_path: j_doc.parent_path,
+        })
+        .collect();
+
+    Ok(RecursiveEmbeddedExtractResult {
+        documents,
+        metadata: j_result.metadata,
+    })
+}
@@ -1,7 +1,22 @@
 use crate::embedded::{EmbeddedDocument, EmbeddedExtractResult};
-use crate::errors::ExtractResult;
-use crate::tika::parse_embedded_optimized::extract_embedded_optimized;
+use crate::errors::{Error, ExtractResult};
+use crate::tika::jni_utils::{jni_call_static_method, jni_new_string_as_jvalue};
+use crate::tika::parse_embedded_optimized::{extract_embedded_optimized, read_i32, read_string};
+use crate::tika::vm;
+use crate::tika::wrappers::{JOfficeParserConfig, JPDFParserConfig, JTesseractOcrConfig};
 use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
+use jni::objects::{JByteArray, JClass, JValue};
+use jni::sys::{jboolean, jlong, JNI_FALSE, JNI_TRUE};
+use jni::{AttachGuard, JNIEnv};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+
+fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>> {
+    let env = vm().attach_current_thread()?;
+    Ok(env)
+}
 
 /// Batch embedded document extraction
 /// Since the Java side currently implements batch as calling optimized with a limit,
@@ -19,6 +34,12 @@ pub fn extract_embedded_batch(
 
 /// Streaming embedded document extraction with callback
 /// Processes embedded documents in batches to reduce memory usage
+///
+/// This still extracts the whole file up front and only batches the
+/// already-collected documents, so peak memory is the full file's embedded
+/// content either way. [`extract_embedded_streaming_callback`] (used by
+/// [`extract_embedded_with_progress`]) is the genuinely incremental version -
+/// prefer that one for files with many large attachments.
 pub fn extract_embedded_streaming<F>(
     file_path: &str,
     pdf_conf: &PdfParserConfig,
@@ -54,6 +75,321 @@ where
     if !batch.is_empty() {
         callback(batch)?;
     }
-    
+
+    Ok(())
+}
+
+/// Extracts embedded documents from `file_path`, invoking `on_progress` as each one
+/// completes with its resource name and the cumulative bytes read across all
+/// documents emitted so far. Returning `false` from the callback stops iteration
+/// early, asking the Java side to stop parsing rather than just trimming an
+/// already-complete result, so callers get a real cancellation point for
+/// building progress bars over large archives.
+///
+/// Built on [`extract_embedded_streaming_callback`], so `on_progress` fires as
+/// each document is parsed rather than being replayed after the fact - unlike
+/// `extract_embedded_streaming` above. Per-document errors and file metadata
+/// aren't available on this path yet (the underlying streaming protocol
+/// doesn't carry them), so `errors` is always empty and `metadata` is empty;
+/// use [`crate::tika::parse_embedded_optimized::extract_embedded_optimized`]
+/// instead if either of those matters more than incremental progress.
+pub fn extract_embedded_with_progress<F>(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    mut on_progress: F,
+) -> ExtractResult<EmbeddedExtractResult>
+where
+    F: FnMut(&str, u64) -> bool,
+{
+    let mut documents = Vec::new();
+    let mut bytes_read: u64 = 0;
+
+    extract_embedded_streaming_callback(
+        file_path,
+        pdf_conf,
+        office_conf,
+        ocr_conf,
+        1,
+        |batch| {
+            for doc in batch {
+                bytes_read += doc.size() as u64;
+                let keep_going = on_progress(&doc.resource_name, bytes_read);
+                documents.push(doc);
+                if !keep_going {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        },
+    )?;
+
+    Ok(EmbeddedExtractResult {
+        documents,
+        errors: Vec::new(),
+        metadata: HashMap::new(),
+    })
+}
+
+/// Extracts embedded documents from every file in `paths` across up to
+/// `max_workers` worker threads, splitting `paths` into contiguous slices so
+/// each worker attaches to the JVM once (via the cached thread-local
+/// `AttachGuard` in `wrappers.rs`) and reuses that attachment for every file
+/// in its slice, instead of paying the attach cost per file.
+///
+/// Results are returned in the same order as `paths`. A failing file yields an
+/// `Err` in its slot rather than aborting the rest of the batch, so one bad
+/// input doesn't take down an otherwise-healthy bulk-ingestion run.
+pub fn extract_embedded_batch_parallel(
+    paths: &[PathBuf],
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    max_workers: usize,
+) -> Vec<ExtractResult<EmbeddedExtractResult>> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+
+    let worker_count = max_workers.max(1).min(paths.len());
+    let chunk_size = paths.len().div_ceil(worker_count);
+
+    let mut results: Vec<Option<ExtractResult<EmbeddedExtractResult>>> =
+        (0..paths.len()).map(|_| None).collect();
+
+    std::thread::scope(|scope| {
+        for (chunk_paths, chunk_results) in paths
+            .chunks(chunk_size)
+            .zip(results.chunks_mut(chunk_size))
+        {
+            scope.spawn(move || {
+                for (path, slot) in chunk_paths.iter().zip(chunk_results.iter_mut()) {
+                    let file_path = path.to_string_lossy();
+                    *slot = Some(extract_embedded_optimized(
+                        &file_path, pdf_conf, office_conf, ocr_conf,
+                    ));
+                }
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|slot| slot.expect("every slot is filled by its worker before the scope ends"))
+        .collect()
+}
+
+/// State threaded through the JVM as an opaque pointer for the duration of a
+/// single [`extract_embedded_streaming_callback`] call, and read back out by
+/// [`Java_ai_yobix_TikaNativeMain_nativeStreamEmbeddedDocument`] as each
+/// embedded document is parsed on the Java side.
+struct StreamingCallbackState<'a> {
+    batch_size: usize,
+    batch: Vec<EmbeddedDocument>,
+    callback: &'a mut dyn FnMut(Vec<EmbeddedDocument>) -> ExtractResult<bool>,
+    /// Set once the user callback has asked to stop, or itself errored, so
+    /// every later invocation on this call short-circuits without running
+    /// user code again.
+    stop: bool,
+    error: Option<Error>,
+}
+
+impl<'a> StreamingCallbackState<'a> {
+    /// Pushes `doc` onto the pending batch, flushing it through `callback`
+    /// once it reaches `batch_size`. Returns whether the Java side should keep
+    /// streaming documents.
+    fn push(&mut self, doc: EmbeddedDocument) -> bool {
+        if self.stop {
+            return false;
+        }
+
+        self.batch.push(doc);
+        if self.batch.len() < self.batch_size {
+            return true;
+        }
+
+        match (self.callback)(std::mem::take(&mut self.batch)) {
+            Ok(keep_going) => {
+                self.stop = !keep_going;
+                keep_going
+            }
+            Err(e) => {
+                self.error = Some(e);
+                self.stop = true;
+                false
+            }
+        }
+    }
+}
+
+/// Decodes a single streamed document record: `[name_len][name][type_len][type]
+/// [rel_id_len][rel_id][content_len][content]`. Unlike the packed format in
+/// `parse_embedded_optimized.rs`, content is stored inline rather than through a
+/// blob table, since a document arriving one at a time has no siblings to
+/// dedupe against yet.
+fn decode_streamed_document(data: &[u8]) -> ExtractResult<EmbeddedDocument> {
+    let mut cursor = Cursor::new(data);
+
+    let resource_name = read_string(&mut cursor)?;
+    let content_type = read_string(&mut cursor)?;
+    let embedded_relationship_id = read_string(&mut cursor)?;
+    let embedded_relationship_id = if embedded_relationship_id.is_empty() {
+        None
+    } else {
+        Some(embedded_relationship_id)
+    };
+
+    let content_len = read_i32(&mut cursor)?;
+    let mut content = vec![0u8; content_len as usize];
+    cursor
+        .read_exact(&mut content)
+        .map_err(|e| Error::ParseError(format!("Failed to read streamed document content: {}", e)))?;
+
+    let content_hash = crate::embedded::hash_content(&content);
+
+    Ok(EmbeddedDocument {
+        resource_name,
+        content_type,
+        content,
+        embedded_relationship_id,
+        children: Vec::new(),
+        depth: 0,
+        parent_path: None,
+        content_hash,
+    })
+}
+
+/// Extracts embedded documents from `file_path`, invoking `callback` once per
+/// `batch_size` documents as they're parsed on the Java side, instead of
+/// collecting every document in memory before batching (as
+/// [`extract_embedded_streaming`] currently does). Peak memory stays roughly
+/// `batch_size` documents regardless of how many embedded attachments the file
+/// holds.
+///
+/// `callback` returning `Ok(false)` asks the Java side to stop feeding
+/// documents early; any remaining embedded documents are left unparsed rather
+/// than collected. A pending partial batch (smaller than `batch_size`) is
+/// still flushed through `callback` once before returning, unless `callback`
+/// itself asked to stop.
+pub fn extract_embedded_streaming_callback<F>(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    batch_size: usize,
+    mut callback: F,
+) -> ExtractResult<()>
+where
+    F: FnMut(Vec<EmbeddedDocument>) -> ExtractResult<bool>,
+{
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+
+    let mut state = StreamingCallbackState {
+        batch_size: batch_size.max(1),
+        batch: Vec::with_capacity(batch_size.max(1)),
+        callback: &mut callback,
+        stop: false,
+        error: None,
+    };
+
+    // Safety: `handle` is only dereferenced by
+    // `Java_ai_yobix_TikaNativeMain_nativeStreamEmbeddedDocument`, which is only
+    // called synchronously by the Java side from within this JNI call below, so
+    // `state` is guaranteed to outlive every use of the pointer.
+    let handle = std::ptr::addr_of_mut!(state) as jlong;
+
+    jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractEmbeddedStreaming",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        J\
+        )V",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            JValue::Long(handle),
+        ],
+    )?;
+
+    if let Some(e) = state.error.take() {
+        return Err(e);
+    }
+
+    if !state.batch.is_empty() && !state.stop {
+        (state.callback)(std::mem::take(&mut state.batch))?;
+    }
+
     Ok(())
+}
+
+/// JNI entry point the Java side calls once per embedded document as
+/// `EmbeddedDocumentExtractor` parses it, instead of collecting every document
+/// into a Java list before returning control to Rust. `handle` is the
+/// `StreamingCallbackState` pointer smuggled down through
+/// [`extract_embedded_streaming_callback`]'s trailing `J` argument; `packed_doc`
+/// is one record in the layout [`decode_streamed_document`] reads.
+///
+/// Returns `JNI_FALSE` to ask the Java side to stop streaming early (the user's
+/// callback returned `false`, errored, or a prior call already did), `JNI_TRUE`
+/// to keep going. A malformed record also stops the stream, and
+/// [`extract_embedded_streaming_callback`] then returns its decode error;
+/// batches already passed to the callback stay delivered, while the pending
+/// partial batch is not flushed.
+///
+/// # Safety
+/// `handle` must be a live `*mut StreamingCallbackState` obtained from
+/// `extract_embedded_streaming_callback`, and this must be called on the same
+/// thread that call is running on - both of which the Java side guarantees by
+/// construction, since it only invokes this synchronously while running the
+/// static method that received `handle`.
+#[no_mangle]
+pub extern "system" fn Java_ai_yobix_TikaNativeMain_nativeStreamEmbeddedDocument<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    packed_doc: JByteArray<'local>,
+) -> jboolean {
+    let outcome = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        // Safety: see function-level safety doc above.
+        let state = unsafe { &mut *(handle as *mut StreamingCallbackState<'_>) };
+        if state.stop {
+            return false;
+        }
+
+        let mut env = match get_vm_attach_current_thread() {
+            Ok(env) => env,
+            Err(_) => return false,
+        };
+        let bytes = match env.convert_byte_array(&packed_doc) {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
+
+        match decode_streamed_document(&bytes) {
+            Ok(doc) => state.push(doc),
+            Err(e) => {
+                state.error = Some(e);
+                state.stop = true;
+                false
+            }
+        }
+    }));
+
+    if outcome.unwrap_or(false) {
+        JNI_TRUE
+    } else {
+        JNI_FALSE
+    }
 }
\ No newline at end of file
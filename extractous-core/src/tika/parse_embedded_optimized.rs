@@ -1,90 +1,283 @@
-use crate::embedded::{EmbeddedDocument, EmbeddedExtractResult};
+use crate::embedded::{
+    EmbeddedDocument, EmbeddedDocumentError, EmbeddedDocumentErrorKind, EmbeddedExtractResult,
+};
 use crate::errors::{Error, ExtractResult};
 use crate::tika::jni_utils::{jni_call_static_method, jni_new_string_as_jvalue};
-use crate::tika::vm;
-use crate::tika::wrappers::{JOptimizedResult, JOfficeParserConfig, JPDFParserConfig, JTesseractOcrConfig};
+use crate::tika::wrappers::{
+    with_cached_env, JOptimizedResult, JOfficeParserConfig, JPDFParserConfig, JTesseractOcrConfig,
+};
 use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
-use jni::{AttachGuard};
-use std::collections::HashMap;
+use jni::objects::JValue;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{Cursor, Read};
+use std::sync::Arc;
 
-fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>> {
-    let env = vm().attach_current_thread()?;
-    Ok(env)
+/// Optimized embedded document extraction that minimizes JNI overhead.
+///
+/// Runs through [`with_cached_env`] rather than attaching fresh each call, so
+/// repeated calls on the same thread - e.g. one per file inside
+/// [`crate::tika::parse_embedded_batch::extract_embedded_batch_parallel`]'s
+/// worker threads - reuse that thread's `AttachGuard` instead of paying the
+/// attach/detach cost every time.
+pub fn extract_embedded_optimized(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+) -> ExtractResult<EmbeddedExtractResult> {
+    with_cached_env(|env| {
+        // Create Java string for file path
+        let file_path_val = jni_new_string_as_jvalue(env, file_path)?;
+
+        // Create Java config objects
+        let j_pdf_conf = JPDFParserConfig::new(env, pdf_conf)?;
+        let j_office_conf = JOfficeParserConfig::new(env, office_conf)?;
+        let j_ocr_conf = JTesseractOcrConfig::new(env, ocr_conf)?;
+
+        // Call the optimized Java method
+        let result = jni_call_static_method(
+            env,
+            "ai/yobix/TikaNativeMain",
+            "extractEmbeddedOptimized",
+            "(Ljava/lang/String;\
+            Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+            Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+            Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+            )Lai/yobix/OptimizedEmbeddedExtractor$OptimizedResult;",
+            &[
+                (&file_path_val).into(),
+                (&j_pdf_conf.internal).into(),
+                (&j_office_conf.internal).into(),
+                (&j_ocr_conf.internal).into(),
+            ],
+        )?;
+
+        let result_obj = result.l()?;
+
+        // Convert Java result to Rust
+        let j_result = JOptimizedResult::new(env, result_obj)?;
+
+        // Check for errors
+        if j_result.error_code != 0 {
+            let error_msg = j_result.error_message.unwrap_or_else(|| {
+                format!("Optimized embedded extraction failed with code {}", j_result.error_code)
+            });
+            return Err(Error::ParseError(error_msg));
+        }
+
+        // Unpack the optimized data
+        let packed_data = j_result.packed_data.ok_or_else(|| {
+            Error::ParseError("No packed data returned from optimized extraction".to_string())
+        })?;
+
+        // Parse the packed data format - see `unpack_optimized_data` for the layout.
+        let (documents, errors) = unpack_optimized_data(&packed_data, j_result.document_count)?;
+
+        // Create empty metadata for now (could be enhanced to include parent metadata)
+        let metadata = HashMap::new();
+
+        Ok(EmbeddedExtractResult {
+            documents,
+            errors,
+            metadata,
+        })
+    })
 }
 
-/// Optimized embedded document extraction that minimizes JNI overhead
-pub fn extract_embedded_optimized(
+/// Optimized embedded document extraction operating on in-memory bytes instead
+/// of a file path, used by [`extract_embedded_optimized_recursive`] to re-run
+/// extraction on a nested container's content without round-tripping it through
+/// disk.
+fn extract_embedded_optimized_from_bytes(
+    buffer: &[u8],
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+) -> ExtractResult<EmbeddedExtractResult> {
+    with_cached_env(|env| {
+        // Note: new_direct_byte_buffer requires a mutable pointer, so we need to copy the data
+        let mut buffer_copy = buffer.to_vec();
+        let byte_buffer =
+            unsafe { env.new_direct_byte_buffer(buffer_copy.as_mut_ptr(), buffer_copy.len())? };
+
+        let j_pdf_conf = JPDFParserConfig::new(env, pdf_conf)?;
+        let j_office_conf = JOfficeParserConfig::new(env, office_conf)?;
+        let j_ocr_conf = JTesseractOcrConfig::new(env, ocr_conf)?;
+
+        let result = jni_call_static_method(
+            env,
+            "ai/yobix/TikaNativeMain",
+            "extractEmbeddedOptimizedFromBytes",
+            "(Ljava/nio/ByteBuffer;\
+            Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+            Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+            Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+            )Lai/yobix/OptimizedEmbeddedExtractor$OptimizedResult;",
+            &[
+                JValue::Object(&byte_buffer),
+                (&j_pdf_conf.internal).into(),
+                (&j_office_conf.internal).into(),
+                (&j_ocr_conf.internal).into(),
+            ],
+        )?;
+
+        let result_obj = result.l()?;
+        let j_result = JOptimizedResult::new(env, result_obj)?;
+
+        if j_result.error_code != 0 {
+            let error_msg = j_result.error_message.unwrap_or_else(|| {
+                format!(
+                    "Optimized embedded extraction from bytes failed with code {}",
+                    j_result.error_code
+                )
+            });
+            return Err(Error::ParseError(error_msg));
+        }
+
+        let packed_data = j_result.packed_data.ok_or_else(|| {
+            Error::ParseError("No packed data returned from optimized extraction".to_string())
+        })?;
+
+        let (documents, errors) = unpack_optimized_data(&packed_data, j_result.document_count)?;
+
+        Ok(EmbeddedExtractResult {
+            documents,
+            errors,
+            metadata: HashMap::new(),
+        })
+    })
+}
+
+/// Default recursion limit for [`extract_embedded_optimized_recursive`].
+pub const DEFAULT_MAX_OPTIMIZED_RECURSIVE_DEPTH: usize = 5;
+
+/// Like [`extract_embedded_optimized`], but walks into nested containers
+/// breadth-first instead of returning a flat, one-level list. Top-level
+/// documents are seeded onto a worklist tagged with a path string (`"0"`,
+/// `"1"`, ...); popping a document that's itself a container (per
+/// [`EmbeddedDocument::is_container`]) re-runs extraction on its bytes and
+/// pushes its children back onto the worklist tagged `"<parent path>/<index>"`,
+/// e.g. `"2/0"` for the first document nested inside the third top-level one.
+///
+/// A content-hash visited-set skips expanding any document whose bytes were
+/// already seen (the document itself is still emitted), guarding against
+/// self-referential containers cycling forever. `max_depth`
+/// caps how many levels are walked; a container at the depth limit is still
+/// emitted, just as a leaf (its own bytes, not descended into), so content two
+/// or more levels deep is never silently dropped.
+///
+/// Returns one flat `Vec<EmbeddedDocument>` with `depth` and `parent_path` set
+/// on every entry so callers can reconstruct the tree, rather than nesting
+/// documents under `children` (see `extract_embedded_from_file_recursive` in
+/// `parse_embedded.rs` for that style instead).
+pub fn extract_embedded_optimized_recursive(
     file_path: &str,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    max_depth: usize,
 ) -> ExtractResult<EmbeddedExtractResult> {
-    let mut env = get_vm_attach_current_thread()?;
-    
-    // Create Java string for file path
-    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
-    
-    // Create Java config objects
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
-    
-    // Call the optimized Java method
-    let result = jni_call_static_method(
-        &mut env,
-        "ai/yobix/TikaNativeMain",
-        "extractEmbeddedOptimized",
-        "(Ljava/lang/String;\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        )Lai/yobix/OptimizedEmbeddedExtractor$OptimizedResult;",
-        &[
-            (&file_path_val).into(),
-            (&j_pdf_conf.internal).into(),
-            (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-        ],
-    )?;
-    
-    let result_obj = result.l()?;
-    
-    // Convert Java result to Rust
-    let j_result = JOptimizedResult::new(&mut env, result_obj)?;
-    
-    // Check for errors
-    if j_result.error_code != 0 {
-        let error_msg = j_result.error_message.unwrap_or_else(|| {
-            format!("Optimized embedded extraction failed with code {}", j_result.error_code)
-        });
-        return Err(Error::ParseError(error_msg));
+    let root = extract_embedded_optimized(file_path, pdf_conf, office_conf, ocr_conf)?;
+
+    let mut visited: HashSet<[u8; 32]> = HashSet::new();
+    // Besides its path and document, each worklist entry carries whether it's
+    // still a candidate for expansion - `false` for content whose hash was
+    // already seen elsewhere, so a self-referential or duplicated container
+    // doesn't recurse forever. Unlike gating on `visited` directly, this only
+    // ever suppresses re-expansion: the document itself is always still
+    // pushed into `documents` below, so two distinct documents that happen to
+    // share content (the same logo embedded under two names, say) aren't
+    // silently dropped.
+    let mut worklist: VecDeque<(String, EmbeddedDocument, bool)> = VecDeque::new();
+    let mut errors = root.errors;
+
+    for (index, mut doc) in root.documents.into_iter().enumerate() {
+        doc.depth = 0;
+        doc.parent_path = None;
+        let expandable = visited.insert(doc.content_hash);
+        worklist.push_back((index.to_string(), doc, expandable));
     }
-    
-    // Unpack the optimized data
-    let packed_data = j_result.packed_data.ok_or_else(|| {
-        Error::ParseError("No packed data returned from optimized extraction".to_string())
-    })?;
-    
-    // Parse the packed data format
-    // Format: [count][doc1_size][doc1_data][doc2_size][doc2_data]...
-    // Where each doc_data contains: [name_len][name][type_len][type][rel_id_len][rel_id][content_len][content]
-    let documents = unpack_optimized_data(&packed_data, j_result.document_count)?;
-    
-    // Create empty metadata for now (could be enhanced to include parent metadata)
-    let metadata = HashMap::new();
-    
+
+    let mut documents = Vec::new();
+
+    while let Some((path, doc, expandable)) = worklist.pop_front() {
+        // Tika frequently tags embedded containers generically (e.g.
+        // `application/octet-stream`, `application/x-tika-msoffice`); refine
+        // before testing `is_container`, or such a container is silently
+        // treated as a leaf and never expanded.
+        let candidate = doc.refine_for_recursion();
+        if expandable && doc.depth < max_depth && candidate.is_container() {
+            // A container re-extraction failing outright (rather than one of its
+            // own documents failing, which is reported per-document) still
+            // leaves `doc` itself in the output as a leaf below.
+            if let Ok(nested) = extract_embedded_optimized_from_bytes(
+                &candidate.content,
+                pdf_conf,
+                office_conf,
+                ocr_conf,
+            ) {
+                errors.extend(nested.errors);
+                for (child_index, mut child) in nested.documents.into_iter().enumerate() {
+                    let child_expandable = visited.insert(child.content_hash);
+                    child.depth = doc.depth + 1;
+                    child.parent_path = Some(path.clone());
+                    worklist.push_back((format!("{}/{}", path, child_index), child, child_expandable));
+                }
+            }
+        }
+
+        documents.push(doc);
+    }
+
     Ok(EmbeddedExtractResult {
         documents,
-        metadata,
+        errors,
+        metadata: root.metadata,
     })
 }
 
-/// Unpack the optimized data format into EmbeddedDocument instances
-fn unpack_optimized_data(data: &[u8], expected_count: i32) -> ExtractResult<Vec<EmbeddedDocument>> {
+/// Length, in bytes, of the content hash every document record and blob table
+/// entry in the packed format carries.
+const CONTENT_HASH_LEN: usize = 32;
+
+/// A document record parsed from the packed format's header section, before
+/// its content has been resolved against the trailing blob table.
+struct PendingDocument {
+    resource_name: String,
+    content_type: String,
+    embedded_relationship_id: Option<String>,
+    /// Non-zero when the Java side failed to extract this specific document;
+    /// `content_hash` is meaningless in that case (the blob table carries no
+    /// entry for it).
+    error_code: i32,
+    error_message: Option<String>,
+    content_hash: [u8; 32],
+}
+
+/// Unpacks the optimized data format into successfully extracted
+/// `EmbeddedDocument`s and per-document [`EmbeddedDocumentError`]s.
+///
+/// Format: `[count](doc...)[blob_count](blob...)`, where each `doc` is
+/// `[name_len][name][type_len][type][rel_id_len][rel_id][error_code:4]
+/// [error_msg_len][error_msg][hash:32][flag:1]` and each `blob` is
+/// `[hash:32][len][bytes]`. A document with `error_code != 0` carries no
+/// content - `hash`/`flag` are present but unused padding - and is reported in
+/// the returned error list instead of the document list, so one corrupt or
+/// encrypted attachment doesn't discard the siblings that parsed fine.
+/// Documents carry a content hash rather than inline bytes; the trailing blob
+/// table stores each unique content blob exactly once, so a document repeated
+/// many times over (a logo embedded throughout a presentation, say) costs only
+/// one JNI transfer instead of one per occurrence - `EmbeddedDocument::content`
+/// is a plain `Vec<u8>`, though, so each document resolved against the table
+/// still gets its own heap allocation cloned out of it. The `flag` byte is
+/// reserved for a future inline-content fallback and is currently always `1`
+/// for successful documents.
+fn unpack_optimized_data(
+    data: &[u8],
+    expected_count: i32,
+) -> ExtractResult<(Vec<EmbeddedDocument>, Vec<EmbeddedDocumentError>)> {
     let mut cursor = Cursor::new(data);
-    let mut documents = Vec::with_capacity(expected_count as usize);
-    
+
     // Read document count
     let count = read_i32(&mut cursor)?;
     if count != expected_count {
@@ -93,8 +286,10 @@ fn unpack_optimized_data(data: &[u8], expected_count: i32) -> ExtractResult<Vec<
             expected_count, count
         )));
     }
-    
-    // Read each document
+
+    // Read each document's header fields; content is resolved afterwards
+    // against the blob table.
+    let mut pending = Vec::with_capacity(count as usize);
     for _ in 0..count {
         let resource_name = read_string(&mut cursor)?;
         let content_type = read_string(&mut cursor)?;
@@ -104,26 +299,104 @@ fn unpack_optimized_data(data: &[u8], expected_count: i32) -> ExtractResult<Vec<
         } else {
             Some(embedded_relationship_id)
         };
-        
-        let content_len = read_i32(&mut cursor)?;
-        let mut content = vec![0u8; content_len as usize];
-        cursor.read_exact(&mut content).map_err(|e| {
-            Error::ParseError(format!("Failed to read content: {}", e))
-        })?;
-        
-        documents.push(EmbeddedDocument {
+
+        let error_code = read_i32(&mut cursor)?;
+        let error_message = read_string(&mut cursor)?;
+        let error_message = if error_message.is_empty() {
+            None
+        } else {
+            Some(error_message)
+        };
+
+        let content_hash = read_content_hash(&mut cursor)?;
+        let _flag = read_u8(&mut cursor)?;
+
+        pending.push(PendingDocument {
             resource_name,
             content_type,
-            content,
             embedded_relationship_id,
+            error_code,
+            error_message,
+            content_hash,
         });
     }
-    
-    Ok(documents)
+
+    // Trailing blob table: parse into a hash -> blob map holding each unique
+    // blob once. Documents resolved against it below still each get their own
+    // `Vec<u8>` copy of the blob; they do not share the map's allocation.
+    let blob_count = read_i32(&mut cursor)?;
+    let mut blobs: HashMap<[u8; 32], Arc<Vec<u8>>> = HashMap::with_capacity(blob_count.max(0) as usize);
+    for _ in 0..blob_count {
+        let hash = read_content_hash(&mut cursor)?;
+        let len = read_i32(&mut cursor)?;
+        let mut bytes = vec![0u8; len as usize];
+        cursor
+            .read_exact(&mut bytes)
+            .map_err(|e| Error::ParseError(format!("Failed to read blob content: {}", e)))?;
+        blobs.insert(hash, Arc::new(bytes));
+    }
+
+    let mut documents = Vec::with_capacity(pending.len());
+    let mut errors = Vec::new();
+    for doc in pending {
+        if doc.error_code != 0 {
+            errors.push(EmbeddedDocumentError {
+                resource_name: doc.resource_name,
+                embedded_relationship_id: doc.embedded_relationship_id,
+                kind: EmbeddedDocumentErrorKind::from_code(doc.error_code),
+                message: doc.error_message.unwrap_or_else(|| {
+                    format!("Embedded document extraction failed with code {}", doc.error_code)
+                }),
+            });
+            continue;
+        }
+
+        let content = blobs.get(&doc.content_hash).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Embedded document \"{}\" references a content hash missing from the blob table",
+                doc.resource_name
+            ))
+        })?;
+
+        documents.push(EmbeddedDocument {
+            resource_name: doc.resource_name,
+            content_type: doc.content_type,
+            content: (**content).clone(),
+            embedded_relationship_id: doc.embedded_relationship_id,
+            children: Vec::new(),
+            depth: 0,
+            parent_path: None,
+            content_hash: doc.content_hash,
+        });
+    }
+
+    Ok((documents, errors))
+}
+
+/// Reads a fixed-length content hash from the cursor.
+fn read_content_hash(cursor: &mut Cursor<&[u8]>) -> ExtractResult<[u8; CONTENT_HASH_LEN]> {
+    let mut buf = [0u8; CONTENT_HASH_LEN];
+    cursor
+        .read_exact(&mut buf)
+        .map_err(|e| Error::ParseError(format!("Failed to read content hash: {}", e)))?;
+    Ok(buf)
+}
+
+/// Reads a single flag byte from the cursor.
+fn read_u8(cursor: &mut Cursor<&[u8]>) -> ExtractResult<u8> {
+    let mut buf = [0u8; 1];
+    cursor
+        .read_exact(&mut buf)
+        .map_err(|e| Error::ParseError(format!("Failed to read flag byte: {}", e)))?;
+    Ok(buf[0])
 }
 
 /// Read a 32-bit integer from the cursor (big-endian)
-fn read_i32(cursor: &mut Cursor<&[u8]>) -> ExtractResult<i32> {
+///
+/// `pub(crate)` so [`crate::tika::parse_embedded_batch`] can decode the
+/// single-document records it streams off the JNI callback path using the
+/// same length-prefix conventions as the packed format here.
+pub(crate) fn read_i32(cursor: &mut Cursor<&[u8]>) -> ExtractResult<i32> {
     let mut buf = [0u8; 4];
     cursor.read_exact(&mut buf).map_err(|e| {
         Error::ParseError(format!("Failed to read i32: {}", e))
@@ -132,7 +405,7 @@ fn read_i32(cursor: &mut Cursor<&[u8]>) -> ExtractResult<i32> {
 }
 
 /// Read a string from the cursor (length-prefixed UTF-8)
-fn read_string(cursor: &mut Cursor<&[u8]>) -> ExtractResult<String> {
+pub(crate) fn read_string(cursor: &mut Cursor<&[u8]>) -> ExtractResult<String> {
     let len = read_i32(cursor)?;
     let mut buf = vec![0u8; len as usize];
     cursor.read_exact(&mut buf).map_err(|e| {
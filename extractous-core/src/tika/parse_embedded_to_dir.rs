@@ -0,0 +1,391 @@
+use crate::embedded::{content_type_to_extension, has_extension, sanitize_resource_name};
+use crate::errors::{Error, ExtractResult};
+use crate::tika::jni_utils::{jni_call_static_method, jni_new_string_as_jvalue};
+use crate::tika::parse_embedded_optimized::{read_i32, read_string};
+use crate::tika::vm;
+use crate::tika::wrappers::{JOfficeParserConfig, JPDFParserConfig, JTesseractOcrConfig};
+use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
+use jni::objects::{JByteArray, JClass, JValue};
+use jni::sys::{jboolean, jlong, JNI_FALSE, JNI_TRUE};
+use jni::{AttachGuard, JNIEnv};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Cursor, Write};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+
+fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>> {
+    let env = vm().attach_current_thread()?;
+    Ok(env)
+}
+
+/// Default size, in bytes, of the buffered writer used for each output file.
+const DEFAULT_BUF_SIZE: usize = 64 * 1024;
+
+/// Tuning knobs for [`extract_embedded_to_dir`].
+#[derive(Debug, Clone)]
+pub struct ExtractToDirOptions {
+    /// Bytes buffered in memory per open file before they're flushed to disk.
+    /// Does not bound total memory use across documents - only per-document.
+    pub buf_size: usize,
+}
+
+impl Default for ExtractToDirOptions {
+    fn default() -> Self {
+        Self {
+            buf_size: DEFAULT_BUF_SIZE,
+        }
+    }
+}
+
+/// One file written by [`extract_embedded_to_dir`]: the resolved on-disk path and
+/// the metadata [`crate::embedded::EmbeddedDocument`] would otherwise have carried
+/// in memory.
+#[derive(Debug, Clone)]
+pub struct EmbeddedDocumentRef {
+    pub resource_name: String,
+    pub content_type: String,
+    pub embedded_relationship_id: Option<String>,
+    /// Where this document's content was written, relative to the `out_dir`
+    /// passed to [`extract_embedded_to_dir`].
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// The result of [`extract_embedded_to_dir`]: every document that was written to
+/// disk, plus any that failed individually (see [`EmbeddedDocumentError`]).
+///
+/// [`EmbeddedDocumentError`]: crate::embedded::EmbeddedDocumentError
+#[derive(Debug, Clone, Default)]
+pub struct ExtractToDirResult {
+    pub documents: Vec<EmbeddedDocumentRef>,
+    pub errors: Vec<crate::embedded::EmbeddedDocumentError>,
+}
+
+/// A file currently being streamed to disk: open handle plus the filename it was
+/// opened under, so [`DirStreamState::end_document`] can report where the bytes
+/// ended up.
+struct OpenDocument {
+    resource_name: String,
+    content_type: String,
+    embedded_relationship_id: Option<String>,
+    file: BufWriter<File>,
+    path: PathBuf,
+    bytes_written: u64,
+}
+
+/// State threaded through the JVM as an opaque pointer for the duration of a
+/// single [`extract_embedded_to_dir`] call, and driven by
+/// [`Java_ai_yobix_TikaNativeMain_nativeStreamEmbeddedToDir`] as the Java side
+/// emits one tagged record per start/chunk/end event.
+struct DirStreamState {
+    out_dir: PathBuf,
+    buf_size: usize,
+    current: Option<OpenDocument>,
+    used_names: HashMap<String, usize>,
+    result: ExtractToDirResult,
+    error: Option<Error>,
+}
+
+impl DirStreamState {
+    /// Opens a new output file for an about-to-be-streamed document. Any
+    /// previously open document that never saw an end-of-document record (the
+    /// Java side errored mid-stream) is dropped as a partial, undeclared file.
+    fn start_document(
+        &mut self,
+        resource_name: String,
+        content_type: String,
+        embedded_relationship_id: Option<String>,
+    ) -> ExtractResult<()> {
+        self.current = None;
+
+        let filename = self.resolve_unique_filename(&resource_name, &content_type);
+        let path = self.out_dir.join(&filename);
+        let file = open_new_file(&path)?;
+
+        self.current = Some(OpenDocument {
+            resource_name,
+            content_type,
+            embedded_relationship_id,
+            file: BufWriter::with_capacity(self.buf_size, file),
+            path: PathBuf::from(filename),
+            bytes_written: 0,
+        });
+
+        Ok(())
+    }
+
+    fn write_chunk(&mut self, chunk: &[u8]) -> ExtractResult<()> {
+        let doc = self
+            .current
+            .as_mut()
+            .ok_or_else(|| Error::ParseError("content chunk with no open document".to_string()))?;
+
+        doc.file
+            .write_all(chunk)
+            .map_err(|e| Error::IoError(e.to_string()))?;
+        doc.bytes_written += chunk.len() as u64;
+
+        Ok(())
+    }
+
+    /// Closes the currently open document successfully, recording it in the
+    /// result. `error_code`/`error_message` (from the Java side's per-document
+    /// error report) route it to `result.errors` instead, and the partial file
+    /// already written is left on disk rather than cleaned up, since a partial
+    /// extraction may still be useful to the caller.
+    fn end_document(
+        &mut self,
+        error_code: i32,
+        error_message: Option<String>,
+    ) -> ExtractResult<()> {
+        let doc = self
+            .current
+            .take()
+            .ok_or_else(|| Error::ParseError("end-of-document with no open document".to_string()))?;
+
+        doc.file.flush().map_err(|e| Error::IoError(e.to_string()))?;
+        doc.file
+            .get_ref()
+            .sync_all()
+            .map_err(|e| Error::IoError(e.to_string()))?;
+
+        if error_code != 0 {
+            self.result.errors.push(crate::embedded::EmbeddedDocumentError {
+                resource_name: doc.resource_name,
+                embedded_relationship_id: doc.embedded_relationship_id,
+                kind: crate::embedded::EmbeddedDocumentErrorKind::from_code(error_code),
+                message: error_message.unwrap_or_else(|| {
+                    format!("Embedded document extraction failed with code {}", error_code)
+                }),
+            });
+            return Ok(());
+        }
+
+        self.result.documents.push(EmbeddedDocumentRef {
+            resource_name: doc.resource_name,
+            content_type: doc.content_type,
+            embedded_relationship_id: doc.embedded_relationship_id,
+            path: doc.path,
+            size: doc.bytes_written,
+        });
+
+        Ok(())
+    }
+
+    /// Works out the filename to write `resource_name`'s content under,
+    /// reusing the same sanitization and extension inference
+    /// `save_all_to_directory` uses, plus a numeric suffix so two documents
+    /// that sanitize to the same name (e.g. both originally empty) don't
+    /// clobber each other.
+    fn resolve_unique_filename(&mut self, resource_name: &str, content_type: &str) -> String {
+        let index = self.result.documents.len() + self.result.errors.len();
+        let base = if !resource_name.is_empty() {
+            sanitize_resource_name(resource_name)
+        } else {
+            format!("embedded_{}", index)
+        };
+
+        let base = if has_extension(&base) {
+            base
+        } else {
+            match content_type_to_extension(content_type) {
+                Some(ext) => format!("{}.{}", base, ext),
+                None => base,
+            }
+        };
+
+        let count = self.used_names.entry(base.clone()).or_insert(0);
+        let filename = if *count == 0 {
+            base.clone()
+        } else {
+            format!("{}.{}", base, count)
+        };
+        *count += 1;
+
+        filename
+    }
+}
+
+/// Creates the output file for `path`, refusing to follow an existing file or
+/// symlink planted at that location ahead of time (a TOCTOU/symlink-escape
+/// guard complementing [`sanitize_resource_name`]'s filename flattening).
+fn open_new_file(path: &Path) -> ExtractResult<File> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| Error::IoError(format!("Failed to create {}: {}", path.display(), e)))
+}
+
+/// Applies one tagged record from the Java side to `state`. Record layout:
+/// `[tag:4]` followed by, per tag -
+///  - `0` (start document): `[name_len][name][type_len][type][rel_id_len][rel_id]`
+///  - `1` (content chunk): `[chunk_len:4][chunk bytes]`
+///  - `2` (end document): `[error_code:4][error_msg_len][error_msg]`
+fn apply_record(state: &mut DirStreamState, data: &[u8]) -> ExtractResult<()> {
+    let mut cursor = Cursor::new(data);
+    let tag = read_i32(&mut cursor)?;
+
+    match tag {
+        0 => {
+            let resource_name = read_string(&mut cursor)?;
+            let content_type = read_string(&mut cursor)?;
+            let embedded_relationship_id = read_string(&mut cursor)?;
+            let embedded_relationship_id = if embedded_relationship_id.is_empty() {
+                None
+            } else {
+                Some(embedded_relationship_id)
+            };
+
+            state.start_document(resource_name, content_type, embedded_relationship_id)
+        }
+        1 => {
+            let chunk_len = read_i32(&mut cursor)?;
+            let start = cursor.position() as usize;
+            let end = start + chunk_len as usize;
+            let chunk = data.get(start..end).ok_or_else(|| {
+                Error::ParseError("content chunk length exceeds record size".to_string())
+            })?;
+
+            state.write_chunk(chunk)
+        }
+        2 => {
+            let error_code = read_i32(&mut cursor)?;
+            let error_message = read_string(&mut cursor)?;
+            let error_message = if error_message.is_empty() {
+                None
+            } else {
+                Some(error_message)
+            };
+
+            state.end_document(error_code, error_message)
+        }
+        other => Err(Error::ParseError(format!(
+            "Unknown streamed-to-dir record tag: {}",
+            other
+        ))),
+    }
+}
+
+/// Extracts embedded documents from `file_path` and writes each one's content
+/// straight to `out_dir` as it's parsed on the Java side, instead of collecting
+/// every document's bytes in memory first (as [`crate::tika::parse_embedded_optimized::extract_embedded_optimized`]
+/// does). Peak memory is bounded by `options.buf_size` plus whatever the Java
+/// side buffers per document, regardless of how large or numerous the
+/// embedded attachments are.
+///
+/// A document that fails individually on the Java side is reported in
+/// [`ExtractToDirResult::errors`] rather than aborting the whole extraction, so
+/// one corrupt attachment doesn't discard everything already written.
+pub fn extract_embedded_to_dir(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    out_dir: &Path,
+    options: &ExtractToDirOptions,
+) -> ExtractResult<ExtractToDirResult> {
+    std::fs::create_dir_all(out_dir).map_err(|e| Error::IoError(e.to_string()))?;
+
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+
+    let mut state = DirStreamState {
+        out_dir: out_dir.to_path_buf(),
+        buf_size: options.buf_size.max(1),
+        current: None,
+        used_names: HashMap::new(),
+        result: ExtractToDirResult::default(),
+        error: None,
+    };
+
+    // Safety: `handle` is only dereferenced by
+    // `Java_ai_yobix_TikaNativeMain_nativeStreamEmbeddedToDir`, which is only
+    // called synchronously by the Java side from within this JNI call below, so
+    // `state` is guaranteed to outlive every use of the pointer.
+    let handle = std::ptr::addr_of_mut!(state) as jlong;
+
+    jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractEmbeddedToDir",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        J\
+        )V",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            JValue::Long(handle),
+        ],
+    )?;
+
+    if let Some(e) = state.error.take() {
+        return Err(e);
+    }
+
+    Ok(state.result)
+}
+
+/// JNI entry point the Java side calls once per tagged record (document start,
+/// content chunk, or document end) as it streams embedded documents out,
+/// instead of building a packed in-memory buffer of every document before
+/// returning control to Rust. `handle` is the `DirStreamState` pointer
+/// smuggled down through [`extract_embedded_to_dir`]'s trailing `J` argument;
+/// `packed_record` is one record in the layout [`apply_record`] reads.
+///
+/// Returns `JNI_FALSE` to ask the Java side to stop streaming early (a record
+/// was malformed, or a filesystem error occurred), `JNI_TRUE` to keep going.
+///
+/// # Safety
+/// `handle` must be a live `*mut DirStreamState` obtained from
+/// `extract_embedded_to_dir`, and this must be called on the same thread that
+/// call is running on - both of which the Java side guarantees by
+/// construction, since it only invokes this synchronously while running the
+/// static method that received `handle`.
+#[no_mangle]
+pub extern "system" fn Java_ai_yobix_TikaNativeMain_nativeStreamEmbeddedToDir<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    packed_record: JByteArray<'local>,
+) -> jboolean {
+    let outcome = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        // Safety: see function-level safety doc above.
+        let state = unsafe { &mut *(handle as *mut DirStreamState) };
+        if state.error.is_some() {
+            return false;
+        }
+
+        let bytes = match env.convert_byte_array(&packed_record) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                state.error = Some(Error::JniError(e));
+                return false;
+            }
+        };
+
+        match apply_record(state, &bytes) {
+            Ok(()) => true,
+            Err(e) => {
+                state.error = Some(e);
+                false
+            }
+        }
+    }));
+
+    if outcome.unwrap_or(false) {
+        JNI_TRUE
+    } else {
+        JNI_FALSE
+    }
+}
@@ -1,6 +1,6 @@
 use crate::errors::{Error, ExtractResult};
 use crate::tika::jni_utils::{
-    jni_call_method, jni_jobject_to_string, jni_new_string_as_jvalue,
+    jni_call_method, jni_call_static_method, jni_jobject_to_string, jni_new_string_as_jvalue,
     jni_tika_metadata_to_rust_metadata,
 };
 use crate::tika::vm;
@@ -9,6 +9,147 @@ use bytemuck::cast_slice_mut;
 use jni::objects::{GlobalRef, JByteArray, JObject, JValue};
 use jni::sys::jsize;
 use jni::{AttachGuard, JNIEnv};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// A type-erased Tika config value for the `extra` passthrough map on each
+/// `J*Config` wrapper below, so callers can reach a setter the crate hasn't
+/// wrapped in a typed field yet without waiting on a code change here.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    Bool(bool),
+    Int(i32),
+    String(String),
+}
+
+/// Picks the JNI type signature letter for a `JValue`, so `tika_config!` callers don't have to spell out `"(Z)V"`/`"(I)V"` by hand.
+fn jvalue_signature(value: &JValue) -> &'static str {
+    match value {
+        JValue::Object(_) => "Ljava/lang/Object;",
+        JValue::Byte(_) => "B",
+        JValue::Char(_) => "C",
+        JValue::Short(_) => "S",
+        JValue::Int(_) => "I",
+        JValue::Long(_) => "J",
+        JValue::Bool(_) => "Z",
+        JValue::Float(_) => "F",
+        JValue::Double(_) => "D",
+        JValue::Void => "V",
+    }
+}
+
+/// Expands a set of `"setterName" => value` pairs into the repeated
+/// `jni_call_method(env, &obj, ...)` calls every `J*Config::new` below used to
+/// hand-write one block at a time. The JNI signature is derived from the
+/// `JValue` variant rather than spelled out at each call site.
+///
+/// As with the hand-written calls it replaces, every setter name used here must
+/// still be declared in the `jni-config.json` file, or a java method-not-found
+/// exception is thrown at call time.
+macro_rules! tika_config {
+    ($env:expr, $obj:expr, { $($method:literal => $value:expr),+ $(,)? }) => {
+        $(
+            {
+                let __value = $value;
+                let __sig = format!("({})V", jvalue_signature(&__value));
+                jni_call_method($env, $obj, $method, &__sig, &[__value])?;
+            }
+        )+
+    };
+}
+
+/// Applies each `(setter name, value)` pair in `extra` to `obj`, looking up the
+/// Tika setter reflectively by name and dispatching on the `ConfigValue` variant
+/// to pick the matching JNI signature. This is how `J*Config::new` below expose
+/// Tika options the crate hasn't added a typed field for.
+fn apply_extra_config<'local>(
+    env: &mut JNIEnv<'local>,
+    obj: &JObject<'local>,
+    extra: &HashMap<String, ConfigValue>,
+) -> ExtractResult<()> {
+    for (setter, value) in extra {
+        match value {
+            ConfigValue::Bool(b) => {
+                jni_call_method(env, obj, setter, "(Z)V", &[JValue::from(*b)])?;
+            }
+            ConfigValue::Int(i) => {
+                jni_call_method(env, obj, setter, "(I)V", &[JValue::from(*i)])?;
+            }
+            ConfigValue::String(s) => {
+                let jvalue = jni_new_string_as_jvalue(env, s)?;
+                jni_call_method(env, obj, setter, "(Ljava/lang/String;)V", &[(&jvalue).into()])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+thread_local! {
+    /// Per-thread JNI attachment, reused across calls so `JReaderInputStream::read`
+    /// and `Drop` don't re-attach to the JVM on every call. Each OS thread attaches
+    /// at most once; the guard is detached automatically when the thread exits.
+    static CACHED_ENV: RefCell<Option<AttachGuard<'static>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` against this thread's cached JNI attachment, attaching to the JVM
+/// the first time this thread needs one and reusing that same `AttachGuard` for
+/// every subsequent call on the thread.
+///
+/// `pub(crate)` so callers outside this module - e.g.
+/// [`crate::tika::parse_embedded_optimized::extract_embedded_optimized`], used
+/// by [`crate::tika::parse_embedded_batch::extract_embedded_batch_parallel`]'s
+/// worker threads - can reuse the same per-thread attachment instead of
+/// attaching and detaching on every call.
+pub(crate) fn with_cached_env<T>(f: impl FnOnce(&mut JNIEnv<'static>) -> ExtractResult<T>) -> ExtractResult<T> {
+    CACHED_ENV.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(vm().attach_current_thread().map_err(Error::JniError)?);
+        }
+        f(slot.as_mut().unwrap())
+    })
+}
+
+/// Backing store for a direct `java.nio.ByteBuffer`: Rust-owned memory that the
+/// Java `ReaderInputStream` writes into directly. The address/capacity are
+/// resolved once via JNI `GetDirectBufferAddress`/`GetDirectBufferCapacity` at
+/// creation time, so every read after that just slices into `storage` - no JNI
+/// array copy, no per-read `byte[]` allocation.
+struct JDirectReadBuffer {
+    _storage: Box<[u8]>,
+    java_buffer: GlobalRef,
+    address: *mut u8,
+    capacity: usize,
+}
+
+impl JDirectReadBuffer {
+    fn new(env: &mut AttachGuard<'static>, capacity: usize) -> ExtractResult<Self> {
+        let mut storage = vec![0u8; capacity].into_boxed_slice();
+        let ptr = storage.as_mut_ptr();
+
+        // Safety: `storage` is kept alive alongside `java_buffer` for as long as this
+        // `JDirectReadBuffer` lives, and it's a boxed slice whose address never moves.
+        let byte_buffer = unsafe { env.new_direct_byte_buffer(ptr, capacity)? };
+
+        // Safety: `byte_buffer` was just created above as a direct buffer backed by `storage`.
+        let address = unsafe { env.get_direct_buffer_address(&byte_buffer)? };
+        let capacity = unsafe { env.get_direct_buffer_capacity(&byte_buffer)? as usize };
+        let java_buffer = env.new_global_ref(&byte_buffer)?;
+
+        Ok(Self {
+            _storage: storage,
+            java_buffer,
+            address,
+            capacity,
+        })
+    }
+
+    /// Safety: only valid to call after the Java side has written `len` bytes
+    /// (`len <= self.capacity`) into the buffer returned by [`Self::new`].
+    unsafe fn written(&self, len: usize) -> &[u8] {
+        std::slice::from_raw_parts(self.address, len)
+    }
+}
 
 /// Wrapper for [`JObject`]s that contain `org.apache.commons.io.input.ReaderInputStream`
 /// It saves a GlobalRef to the java object, which is cleared when the last GlobalRef is dropped
@@ -17,91 +158,183 @@ pub struct JReaderInputStream {
     internal: GlobalRef,
     buffer: GlobalRef,
     capacity: jsize,
+    /// Direct `ByteBuffer` fast path, used whenever the JVM supports it and a read
+    /// fits within it. `None` means direct buffers aren't available and every read
+    /// falls back to the heap `byte[]` path via `buffer`.
+    direct_buffer: Option<JDirectReadBuffer>,
+    /// Invoked after each successful read with the total bytes read so far, so
+    /// callers can drive a progress bar. Returning `false` cancels the extraction:
+    /// subsequent reads report end-of-stream instead of calling into Java again.
+    on_progress: Option<Box<dyn FnMut(u64) -> bool + Send>>,
+    total_read: u64,
+    cancelled: bool,
     #[cfg(feature = "stream-attachguard")]
     _guard: AttachGuard<'static>,
 }
 
 impl JReaderInputStream {
     pub(crate) fn new(guard: AttachGuard<'static>, obj: JObject<'_>) -> ExtractResult<Self> {
+        Self::new_with_progress(guard, obj, None)
+    }
+
+    /// Like [`Self::new`], but additionally invokes `on_progress` after each
+    /// successful read with the total bytes read so far, for driving progress
+    /// bars or cancelling a long-running extraction early.
+    pub(crate) fn new_with_progress(
+        mut guard: AttachGuard<'static>,
+        obj: JObject<'_>,
+        on_progress: Option<Box<dyn FnMut(u64) -> bool + Send>>,
+    ) -> ExtractResult<Self> {
         // Creates new jbyte array
         let capacity = DEFAULT_BUF_SIZE as jsize;
         let jbyte_array = guard.new_byte_array(capacity)?;
 
+        // The direct buffer is a pure optimization - if the JVM can't create one for
+        // some reason, fall back to the heap array path below rather than failing.
+        let direct_buffer = JDirectReadBuffer::new(&mut guard, capacity as usize).ok();
+
         Ok(Self {
             internal: guard.new_global_ref(obj)?,
             buffer: guard.new_global_ref(jbyte_array)?,
             capacity,
+            direct_buffer,
+            on_progress,
+            total_read: 0,
+            cancelled: false,
             #[cfg(feature = "stream-attachguard")]
             _guard: guard,
         })
     }
 
+    /// Updates the running byte count and, if a progress callback is set, reports
+    /// it. A `false` return from the callback marks the stream cancelled, so the
+    /// next `read` reports end-of-stream without calling into Java again.
+    fn report_progress(&mut self, bytes_read: usize) {
+        self.total_read += bytes_read as u64;
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            if !on_progress(self.total_read) {
+                self.cancelled = true;
+            }
+        }
+    }
+
     pub(crate) fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let mut env = vm().attach_current_thread().map_err(Error::JniError)?;
+        if self.cancelled {
+            return Ok(0);
+        }
 
         let length = buf.len() as jsize;
 
-        if length > self.capacity {
-            // Create the new byte array with the new capacity
-            let jbyte_array = env
-                .new_byte_array(length as jsize)
-                .map_err(|_e| Error::JniEnvCall("Failed to create byte array"))?;
+        let n = with_cached_env(|env| -> ExtractResult<usize> {
+            let mut direct_unsupported = false;
 
-            self.buffer = env
-                .new_global_ref(jbyte_array)
-                .map_err(|_e| Error::JniEnvCall("Failed to create global reference"))?;
+            if let Some(direct) = self
+                .direct_buffer
+                .as_ref()
+                .filter(|d| length as usize <= d.capacity)
+            {
+                let call_result = jni_call_method(
+                    env,
+                    &self.internal,
+                    "read",
+                    "(Ljava/nio/ByteBuffer;II)I",
+                    &[
+                        JValue::Object(&direct.java_buffer),
+                        JValue::Int(0),
+                        JValue::Int(length),
+                    ],
+                );
+
+                // The direct-buffer read is a pure optimization over the heap byte[]
+                // path below, so if this particular ReaderInputStream doesn't expose a
+                // read(ByteBuffer,int,int) overload (only commons-io's newer versions
+                // do), fall back instead of failing every read outright.
+                match call_result.and_then(|v| v.i().map_err(Error::JniError)) {
+                    Ok(num_read_bytes) => {
+                        if num_read_bytes == -1 {
+                            return Ok(0);
+                        }
+
+                        // Safety: the Java call above just wrote `num_read_bytes` bytes into
+                        // the memory backing `direct`.
+                        let written = unsafe { direct.written(num_read_bytes as usize) };
+                        buf[..num_read_bytes as usize].copy_from_slice(written);
+                        let n = num_read_bytes as usize;
+                        self.report_progress(n);
+                        return Ok(n);
+                    }
+                    Err(_) => {
+                        direct_unsupported = true;
+                    }
+                }
+            }
 
-            self.capacity = length;
-        }
+            if direct_unsupported {
+                // The overload isn't there and won't appear later, so stop paying for a
+                // failed call (and the JNI exception check it triggers) on every read.
+                self.direct_buffer = None;
+            }
 
-        // // Create the java byte array
-        // let length = buf.len() as jsize;
-        // let jbyte_array = env
-        //     .new_byte_array(length)
-        //     .map_err(|_e| Error::JniEnvCall("Failed to create byte array"))?;
-
-        // Call the Java Reader's `read` method
-        let call_result = jni_call_method(
-            &mut env,
-            &self.internal,
-            "read",
-            "([BII)I",
-            &[
-                JValue::Object(&self.buffer),
-                JValue::Int(0),
-                JValue::Int(length),
-            ],
-        );
-        let num_read_bytes = call_result?.i().map_err(Error::JniError)?;
-
-        // Get self.buffer object as a local reference
-        let obj_local = env
-            .new_local_ref(&self.buffer)
-            .map_err(|_e| Error::JniEnvCall("Failed to create local ref"))?;
-
-        // cast because java byte array is i8[]
-        let buf_of_i8: &mut [i8] = cast_slice_mut(buf);
-
-        // Get the bytes from the Java byte array to the Rust byte array
-        // This is a copy or just memory reference. POTENTIAL performance improvement
-        env.get_byte_array_region(JByteArray::from(obj_local), 0, buf_of_i8)
-            .map_err(|_e| Error::JniEnvCall("Failed to get byte array region"))?;
-
-        if num_read_bytes == -1 {
-            // End of stream reached
-            Ok(0)
-        } else {
-            Ok(num_read_bytes as usize)
-        }
+            if length > self.capacity {
+                // Create the new byte array with the new capacity
+                let jbyte_array = env
+                    .new_byte_array(length as jsize)
+                    .map_err(|_e| Error::JniEnvCall("Failed to create byte array"))?;
+
+                self.buffer = env
+                    .new_global_ref(jbyte_array)
+                    .map_err(|_e| Error::JniEnvCall("Failed to create global reference"))?;
+
+                self.capacity = length;
+            }
+
+            // Call the Java Reader's `read` method
+            let call_result = jni_call_method(
+                env,
+                &self.internal,
+                "read",
+                "([BII)I",
+                &[
+                    JValue::Object(&self.buffer),
+                    JValue::Int(0),
+                    JValue::Int(length),
+                ],
+            );
+            let num_read_bytes = call_result?.i().map_err(Error::JniError)?;
+
+            // Get self.buffer object as a local reference
+            let obj_local = env
+                .new_local_ref(&self.buffer)
+                .map_err(|_e| Error::JniEnvCall("Failed to create local ref"))?;
+
+            // cast because java byte array is i8[]
+            let buf_of_i8: &mut [i8] = cast_slice_mut(buf);
+
+            // Get the bytes from the Java byte array to the Rust byte array
+            env.get_byte_array_region(JByteArray::from(obj_local), 0, buf_of_i8)
+                .map_err(|_e| Error::JniEnvCall("Failed to get byte array region"))?;
+
+            if num_read_bytes == -1 {
+                // End of stream reached
+                Ok(0)
+            } else {
+                let n = num_read_bytes as usize;
+                self.report_progress(n);
+                Ok(n)
+            }
+        })?;
+
+        Ok(n)
     }
 }
 
 impl Drop for JReaderInputStream {
     fn drop(&mut self) {
-        if let Ok(mut env) = vm().attach_current_thread() {
-            // Call the Java Reader's `close` method
-            jni_call_method(&mut env, &self.internal, "close", "()V", &[]).ok();
-        }
+        // Call the Java Reader's `close` method
+        let _ = with_cached_env(|env| -> ExtractResult<()> {
+            jni_call_method(env, &self.internal, "close", "()V", &[])?;
+            Ok(())
+        });
     }
 }
 
@@ -214,34 +447,12 @@ impl<'local> JPDFParserConfig<'local> {
         // Call the setters
         // Make sure all of these methods are declared in jni-config.json file, otherwise
         // java method not found exception will be thrown
-        jni_call_method(
-            env,
-            &obj,
-            "setExtractInlineImages",
-            "(Z)V",
-            &[JValue::from(config.extract_inline_images)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setExtractUniqueInlineImagesOnly",
-            "(Z)V",
-            &[JValue::from(config.extract_unique_inline_images_only)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setExtractMarkedContent",
-            "(Z)V",
-            &[JValue::from(config.extract_marked_content)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setExtractAnnotationText",
-            "(Z)V",
-            &[JValue::from(config.extract_annotation_text)],
-        )?;
+        tika_config!(env, &obj, {
+            "setExtractInlineImages" => JValue::from(config.extract_inline_images),
+            "setExtractUniqueInlineImagesOnly" => JValue::from(config.extract_unique_inline_images_only),
+            "setExtractMarkedContent" => JValue::from(config.extract_marked_content),
+            "setExtractAnnotationText" => JValue::from(config.extract_annotation_text),
+        });
         // The PdfOcrStrategy enum names must match the Java org.apache.tika.parser.pdf
         // .PDFParserConfig$OCR_STRATEGY enum names
         let ocr_str_val = jni_new_string_as_jvalue(env, &config.ocr_strategy.to_string())?;
@@ -253,6 +464,8 @@ impl<'local> JPDFParserConfig<'local> {
             &[(&ocr_str_val).into()],
         )?;
 
+        apply_extra_config(env, &obj, &config.extra)?;
+
         Ok(Self { internal: obj })
     }
 }
@@ -276,76 +489,20 @@ impl<'local> JOfficeParserConfig<'local> {
         // Call the setters
         // Make sure all of these methods are declared in jni-config.json file, otherwise
         // java method not found exception will be thrown
-        jni_call_method(
-            env,
-            &obj,
-            "setExtractMacros",
-            "(Z)V",
-            &[JValue::from(config.extract_macros)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeDeletedContent",
-            "(Z)V",
-            &[JValue::from(config.include_deleted_content)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeMoveFromContent",
-            "(Z)V",
-            &[JValue::from(config.include_move_from_content)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeShapeBasedContent",
-            "(Z)V",
-            &[JValue::from(config.include_shape_based_content)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeHeadersAndFooters",
-            "(Z)V",
-            &[JValue::from(config.include_headers_and_footers)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeMissingRows",
-            "(Z)V",
-            &[JValue::from(config.include_missing_rows)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeSlideNotes",
-            "(Z)V",
-            &[JValue::from(config.include_slide_notes)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setIncludeSlideMasterContent",
-            "(Z)V",
-            &[JValue::from(config.include_slide_master_content)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setConcatenatePhoneticRuns",
-            "(Z)V",
-            &[JValue::from(config.concatenate_phonetic_runs)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setExtractAllAlternativesFromMSG",
-            "(Z)V",
-            &[JValue::from(config.extract_all_alternatives_from_msg)],
-        )?;
+        tika_config!(env, &obj, {
+            "setExtractMacros" => JValue::from(config.extract_macros),
+            "setIncludeDeletedContent" => JValue::from(config.include_deleted_content),
+            "setIncludeMoveFromContent" => JValue::from(config.include_move_from_content),
+            "setIncludeShapeBasedContent" => JValue::from(config.include_shape_based_content),
+            "setIncludeHeadersAndFooters" => JValue::from(config.include_headers_and_footers),
+            "setIncludeMissingRows" => JValue::from(config.include_missing_rows),
+            "setIncludeSlideNotes" => JValue::from(config.include_slide_notes),
+            "setIncludeSlideMasterContent" => JValue::from(config.include_slide_master_content),
+            "setConcatenatePhoneticRuns" => JValue::from(config.concatenate_phonetic_runs),
+            "setExtractAllAlternativesFromMSG" => JValue::from(config.extract_all_alternatives_from_msg),
+        });
+
+        apply_extra_config(env, &obj, &config.extra)?;
 
         Ok(Self { internal: obj })
     }
@@ -366,38 +523,23 @@ impl<'local> JTesseractOcrConfig<'local> {
         let class = env.find_class("org/apache/tika/parser/ocr/TesseractOCRConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
+        validate_tesseract_languages(env, &config.language)?;
+
         // Call the setters
         // Make sure all of these methods are declared in jni-config.json file, otherwise
         // java method not found exception will be thrown
-        jni_call_method(
-            env,
-            &obj,
-            "setDensity",
-            "(I)V",
-            &[JValue::from(config.density)],
-        )?;
-        jni_call_method(env, &obj, "setDepth", "(I)V", &[JValue::from(config.depth)])?;
-        jni_call_method(
-            env,
-            &obj,
-            "setTimeoutSeconds",
-            "(I)V",
-            &[JValue::from(config.timeout_seconds)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setEnableImagePreprocessing",
-            "(Z)V",
-            &[JValue::from(config.enable_image_preprocessing)],
-        )?;
-        jni_call_method(
-            env,
-            &obj,
-            "setApplyRotation",
-            "(Z)V",
-            &[JValue::from(config.apply_rotation)],
-        )?;
+        tika_config!(env, &obj, {
+            "setDensity" => JValue::from(config.density),
+            "setDepth" => JValue::from(config.depth),
+            "setTimeoutSeconds" => JValue::from(config.timeout_seconds),
+            "setEnableImagePreprocessing" => JValue::from(config.enable_image_preprocessing),
+            "setApplyRotation" => JValue::from(config.apply_rotation),
+            "setPageSegMode" => JValue::from(config.page_seg_mode),
+            "setOcrEngineMode" => JValue::from(config.ocr_engine_mode),
+            "setMinFileSizeToOcr" => JValue::from(config.min_file_size_bytes),
+            "setMaxFileSizeToOcr" => JValue::from(config.max_file_size_bytes),
+            "setResize" => JValue::from(config.resize_percent),
+        });
 
         let lang_string_val = jni_new_string_as_jvalue(env, &config.language)?;
         jni_call_method(
@@ -408,10 +550,63 @@ impl<'local> JTesseractOcrConfig<'local> {
             &[(&lang_string_val).into()],
         )?;
 
+        if let Some(whitelist) = &config.character_whitelist {
+            let whitelist_val = jni_new_string_as_jvalue(env, whitelist)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setCharacterWhitelist",
+                "(Ljava/lang/String;)V",
+                &[(&whitelist_val).into()],
+            )?;
+        }
+
+        apply_extra_config(env, &obj, &config.extra)?;
+
         Ok(Self { internal: obj })
     }
 }
 
+/// Splits a `+`-joined multi-language string like `"eng+deu"` into the
+/// individual Tesseract language pack codes it requests.
+fn split_tesseract_languages(language: &str) -> Vec<&str> {
+    language
+        .split('+')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Confirms every language pack requested in `language` (a `+`-joined list such
+/// as `"eng+deu"`) is installed and resolvable by Tesseract, surfacing a typed
+/// error naming the missing pack instead of letting Tika silently fall back to
+/// an empty OCR result at extraction time.
+fn validate_tesseract_languages<'local>(
+    env: &mut JNIEnv<'local>,
+    language: &str,
+) -> ExtractResult<()> {
+    for lang in split_tesseract_languages(language) {
+        let lang_val = jni_new_string_as_jvalue(env, lang)?;
+        let available = jni_call_static_method(
+            env,
+            "ai/yobix/TikaNativeMain",
+            "isTesseractLanguageAvailable",
+            "(Ljava/lang/String;)Z",
+            &[(&lang_val).into()],
+        )?
+        .z()?;
+
+        if !available {
+            return Err(Error::ParseError(format!(
+                "Tesseract language pack \"{}\" is not installed",
+                lang
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Wrapper for [`JObject`]s that contain `ai.yobix.EmbeddedExtractResult`.
 pub(crate) struct JEmbeddedExtractResult {
     pub(crate) error_code: u8,
@@ -469,9 +664,20 @@ impl JEmbeddedExtractResult {
             documents.push(JEmbeddedDocument::new(env, doc_obj)?);
         }
 
-        // For now, we'll create empty metadata
-        // TODO: Get metadata from parent document if needed
-        let metadata = Metadata::new();
+        // Get metadata from the parent document, if the Java side provided any
+        let parent_metadata_obj = jni_call_method(
+            env,
+            &obj,
+            "getMetadata",
+            "()Lorg/apache/tika/metadata/Metadata;",
+            &[],
+        )?
+        .l()?;
+        let metadata = if !parent_metadata_obj.is_null() {
+            jni_tika_metadata_to_rust_metadata(env, parent_metadata_obj)?
+        } else {
+            Metadata::new()
+        };
 
         Ok(Self {
             error_code,
@@ -488,6 +694,20 @@ pub(crate) struct JEmbeddedDocument {
     pub(crate) content_type: String,
     pub(crate) content: Vec<u8>,
     pub(crate) embedded_relationship_id: Option<String>,
+    /// This document's own extraction metadata. Empty for a plain (non-recursive)
+    /// extraction; populated with the result of re-parsing `content` through Tika
+    /// when the Java side is asked to recurse (see `extractEmbeddedRecursive`).
+    pub(crate) metadata: Metadata,
+    /// How many containers deep this document was found (0 = top-level).
+    pub(crate) depth: usize,
+    /// Resource path of the parent document this was embedded in, if any.
+    pub(crate) parent_path: Option<String>,
+    /// Non-zero when the Java side failed to extract this specific document
+    /// rather than the whole call; `content`/`metadata` are meaningless in
+    /// that case. See [`crate::embedded::EmbeddedDocumentErrorKind::from_code`]
+    /// for what each value means.
+    pub(crate) error_code: i32,
+    pub(crate) error_message: Option<String>,
 }
 
 impl JEmbeddedDocument {
@@ -532,11 +752,69 @@ impl JEmbeddedDocument {
             None
         };
 
+        // Get this document's own metadata, populated when the extraction was
+        // recursive; `null`/empty for a plain extraction.
+        let metadata_obj = jni_call_method(
+            env,
+            &obj,
+            "getMetadata",
+            "()Lorg/apache/tika/metadata/Metadata;",
+            &[],
+        )?
+        .l()?;
+        let metadata = if !metadata_obj.is_null() {
+            jni_tika_metadata_to_rust_metadata(env, metadata_obj)?
+        } else {
+            Metadata::new()
+        };
+
+        // Get embed depth (0 = top-level)
+        let depth = jni_call_method(env, &obj, "getDepth", "()I", &[])?
+            .i()
+            .unwrap_or(0) as usize;
+
+        // Get parent resource path, if this document was found while recursing
+        let parent_path_obj = jni_call_method(
+            env,
+            &obj,
+            "getParentPath",
+            "()Ljava/lang/String;",
+            &[],
+        )?
+        .l()?;
+        let parent_path = if !parent_path_obj.is_null() {
+            Some(jni_jobject_to_string(env, parent_path_obj)?)
+        } else {
+            None
+        };
+
+        // Get this document's own error code/message, if the Java side failed
+        // to extract it specifically (0 = success).
+        let error_code = jni_call_method(env, &obj, "getErrorCode", "()I", &[])?
+            .i()
+            .unwrap_or(0);
+        let error_message = if error_code != 0 {
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            if !msg_obj.is_null() {
+                Some(jni_jobject_to_string(env, msg_obj)?)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
         Ok(Self {
             resource_name,
             content_type,
             content,
             embedded_relationship_id,
+            metadata,
+            depth,
+            parent_path,
+            error_code,
+            error_message,
         })
     }
 }
@@ -0,0 +1,32 @@
+/// Magic bytes at the start of a ZIP archive (and therefore of every OOXML package,
+/// since `.docx`/`.xlsx`/`.pptx` are ZIPs under the hood).
+pub(crate) const ZIP_MAGIC: [u8; 4] = [0x50, 0x4B, 0x03, 0x04];
+
+/// Disambiguates an OOXML package (`.docx`/`.xlsx`/`.pptx`) by scanning its ZIP
+/// entries for the part name that's unique to each format, rather than fully
+/// parsing the ZIP central directory and `[Content_Types].xml`.
+pub(crate) fn sniff_ooxml_subtype(data: &[u8]) -> Option<&'static str> {
+    const MARKERS: &[(&[u8], &str)] = &[
+        (
+            b"word/document.xml",
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        ),
+        (
+            b"xl/workbook.xml",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        ),
+        (
+            b"ppt/presentation.xml",
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        ),
+    ];
+
+    MARKERS
+        .iter()
+        .find(|(marker, _)| contains_bytes(data, marker))
+        .map(|(_, mime)| *mime)
+}
+
+fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}